@@ -0,0 +1,232 @@
+use crossterm::event::{KeyCode,KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Style,Stylize,Color},
+    symbols::border,
+    text::Line,
+    widgets::{Block,StatefulWidget,Row,Table,Cell,TableState},
+};
+
+use ratatui::layout::Constraint;
+use crate::monitor::{Monitor, Position};
+use crate::utils::{self,TUIMode};
+use crate::App;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Arrangement {
+    Row,
+    Stack,
+    Grid,
+}
+
+impl Arrangement {
+    pub fn table() -> Vec<Self> {
+        vec![
+            Arrangement::Row,
+            Arrangement::Stack,
+            Arrangement::Grid,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Arrangement::Row => "row",
+            Arrangement::Stack => "stack",
+            Arrangement::Grid => "2x2 grid",
+        }
+    }
+
+    // Lays the enabled monitors out edge to edge, top-aligned in rows and
+    // left-aligned in stacks. The primary monitor goes first and keeps its
+    // position, the others follow in their current order. Locked monitors
+    // don't move: without a primary the first one anchors the layout in its
+    // place, the other ones are left out.
+    pub fn apply(&self, monitors: &mut [Monitor]) {
+        let compare = |&a: &usize, &b: &usize| {
+            let (ax, ay, _, _) = monitors[a].get_geometry();
+            let (bx, by, _, _) = monitors[b].get_geometry();
+            let key = |x: f64, y: f64| if *self == Arrangement::Stack { (y, x) } else { (x, y) };
+            monitors[b].primary.cmp(&monitors[a].primary)
+                .then(key(ax, ay).partial_cmp(&key(bx, by)).unwrap_or(std::cmp::Ordering::Equal))
+        };
+        let mut order: Vec<usize> = (0..monitors.len())
+            .filter(|&i| monitors[i].enabled && !monitors[i].locked)
+            .collect();
+        order.sort_by(compare);
+        if !order.first().is_some_and(|&i| monitors[i].primary)
+            && let Some(anchor) = (0..monitors.len())
+                .filter(|&i| monitors[i].enabled && monitors[i].locked)
+                .min_by(compare)
+        {
+            order.insert(0, anchor);
+        }
+
+        let origin = order.first()
+            .filter(|&&i| monitors[i].primary || monitors[i].locked)
+            .and_then(|&i| monitors[i].position.clone())
+            .unwrap_or(Position { x: 0, y: 0 });
+        let columns = match self {
+            Arrangement::Row => order.len().max(1),
+            Arrangement::Stack => 1,
+            Arrangement::Grid => 2,
+        };
+
+        let mut y = origin.y as f64;
+        for row in order.chunks(columns) {
+            let mut x = origin.x as f64;
+            let mut row_height: f64 = 0.0;
+            for &i in row {
+                let (_, _, w, h) = monitors[i].get_geometry();
+                monitors[i].position = Some(Position { x: x.round() as i32, y: y.round() as i32 });
+                x += w;
+                row_height = row_height.max(h);
+            }
+            y += row_height;
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Arrange {
+    pub state: TableState,
+}
+
+impl Arrange {
+    pub fn new(selected: usize) -> Self {
+        Arrange {
+            state: TableState::default()
+                .with_selected(selected),
+        }
+    }
+
+    pub fn handle_events(app:&mut App, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char('k') | KeyCode::Up => Arrange::previous(app),
+            KeyCode::Char('j') | KeyCode::Down => Arrange::next(app),
+            KeyCode::Char(' ') | KeyCode::Enter => Arrange::select(app),
+            KeyCode::Esc => Arrange::change_mode(app,TUIMode::View),
+            _ => {}
+        }
+    }
+
+    fn change_mode(app:&mut App,mode: TUIMode) {
+        app.mode = mode;
+    }
+
+    fn next(app:&mut App) {
+        app.selected_arrangement = utils::next_index(app.selected_arrangement, Arrangement::table().len(), app.config.wrap_navigation);
+    }
+
+    fn previous(app:&mut App) {
+        app.selected_arrangement = utils::previous_index(app.selected_arrangement, Arrangement::table().len(), app.config.wrap_navigation);
+    }
+
+    fn select(app:&mut App) {
+        Arrangement::table()[app.selected_arrangement].apply(&mut app.monitors);
+    }
+
+    fn arrangements_to_rows(&self) -> Vec<Row<'static>> {
+        Arrangement::table()
+            .into_iter()
+            .map(|arrangement| {
+                Row::new(vec![
+                    Cell::default().content(
+                        Line::from(arrangement.name())
+                            .centered()
+                    ),
+                ])
+            })
+            .collect()
+    }
+
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let title = Line::from(" Arrange ".bold());
+        let block = Block::bordered()
+            .title(title.white().centered())
+            .border_set(border::THICK)
+            .border_style(Style::default().fg(Color::Yellow));
+
+        let widths = [
+            Constraint::Percentage(100),
+        ];
+
+        let table = Table::new(self.arrangements_to_rows(),widths)
+            .column_spacing(1)
+            .row_highlight_style(Style::new().yellow())
+            .cell_highlight_style(Style::new().blue())
+            .highlight_symbol("  ")
+            .block(block);
+
+        StatefulWidget::render(
+            table,
+            area,
+            buf,
+            &mut self.state,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::tests::test_monitors;
+
+    // 1920x1080, 1024x576 and 1280x720, scattered
+    fn three_monitors() -> Vec<Monitor> {
+        let mut monitors = test_monitors();
+        monitors[1].enabled = true;
+        monitors[1].position = Some(Position { x: 500, y: 900 });
+        let mut third = monitors[0].clone();
+        third.name = "Monitor 3".to_string();
+        third.scale = Some(1.5);
+        third.position = Some(Position { x: 3000, y: -100 });
+        monitors.push(third);
+        monitors
+    }
+
+    fn positions(monitors: &[Monitor]) -> Vec<(i32, i32)> {
+        monitors.iter()
+            .map(|m| m.position.clone().map(|p| (p.x, p.y)).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn arrange_row() {
+        let mut monitors = three_monitors();
+        Arrangement::Row.apply(&mut monitors);
+        assert_eq!(positions(&monitors), vec![(0, 0), (1920, 0), (2944, 0)]);
+    }
+
+    #[test]
+    fn arrange_stack() {
+        let mut monitors = three_monitors();
+        Arrangement::Stack.apply(&mut monitors);
+        // ordered by y: Monitor 3, Monitor 1, Monitor 2
+        assert_eq!(positions(&monitors), vec![(0, 720), (0, 1800), (0, 0)]);
+    }
+
+    #[test]
+    fn arrange_row_around_locked() {
+        let mut monitors = three_monitors();
+        monitors[2].locked = true;
+        Arrangement::Row.apply(&mut monitors);
+        // Monitor 3 (1280 wide) stays, the others follow it
+        assert_eq!(positions(&monitors), vec![(4280, -100), (6200, -100), (3000, -100)]);
+
+        // the leftmost locked monitor anchors, the other one is left where it is
+        let mut monitors = three_monitors();
+        monitors[1].locked = true;
+        monitors[2].locked = true;
+        Arrangement::Row.apply(&mut monitors);
+        assert_eq!(positions(&monitors), vec![(1524, 900), (500, 900), (3000, -100)]);
+    }
+
+    #[test]
+    fn arrange_grid_around_primary() {
+        let mut monitors = three_monitors();
+        monitors[2].primary = true;
+        Arrangement::Grid.apply(&mut monitors);
+        assert_eq!(positions(&monitors), vec![(4280, -100), (3000, 980), (3000, -100)]);
+    }
+}
@@ -0,0 +1,155 @@
+use std::io;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crate::configuration::Configuration;
+use crate::monitor::Monitor;
+use crate::utils::ConfigFormat;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CliOptions {
+    pub export: bool,
+    pub format: ConfigFormat,
+    pub output: Option<String>,
+    pub verbose: bool,
+    // applied to the loaded layout before exporting
+    pub keys: Vec<KeyEvent>,
+    // JSON file read instead of running wlr-randr
+    pub monitors_from: Option<String>,
+}
+
+impl CliOptions {
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let mut options = CliOptions::default();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--export" => options.export = true,
+                "--verbose" => options.verbose = true,
+                "--format" => {
+                    let name = args.next().ok_or("--format expects hyprland, hyprland-v2, sway or niri")?;
+                    options.format = ConfigFormat::parse(name)
+                        .ok_or(format!("Unknown format `{}`, expected hyprland, hyprland-v2, sway or niri", name))?;
+                }
+                "--output" => {
+                    let path = args.next().ok_or("--output expects a path")?;
+                    options.output = Some(path.clone());
+                }
+                "--monitors-from" => {
+                    let path = args.next().ok_or("--monitors-from expects a path")?;
+                    options.monitors_from = Some(path.clone());
+                }
+                "--keys" => {
+                    let keys = args.next().ok_or("--keys expects a key sequence")?;
+                    options.keys = parse_keys(keys)?;
+                }
+                _ => return Err(format!("Unknown argument `{}`", arg)),
+            }
+        }
+        Ok(options)
+    }
+}
+
+// Keys as typed in the TUI, named ones between brackets: `mLL<Esc>`,
+// `<C-s>`, `<A-l>`. Supported names are Esc, Enter, Space, Backspace,
+// Up, Down, Left and Right.
+pub fn parse_keys(keys: &str) -> Result<Vec<KeyEvent>, String> {
+    let mut events = vec![];
+    let mut chars = keys.chars();
+    while let Some(c) = chars.next() {
+        if c != '<' {
+            events.push(KeyEvent::from(KeyCode::Char(c)));
+            continue;
+        }
+        let name: String = chars.by_ref().take_while(|&c| c != '>').collect();
+        let (modifiers, key) = match name.split_once('-') {
+            Some(("C", key)) => (KeyModifiers::CONTROL, key),
+            Some(("A", key)) => (KeyModifiers::ALT, key),
+            _ => (KeyModifiers::NONE, name.as_str()),
+        };
+        let code = match key {
+            "Esc" => KeyCode::Esc,
+            "Enter" => KeyCode::Enter,
+            "Space" => KeyCode::Char(' '),
+            "Backspace" => KeyCode::Backspace,
+            "Up" => KeyCode::Up,
+            "Down" => KeyCode::Down,
+            "Left" => KeyCode::Left,
+            "Right" => KeyCode::Right,
+            _ if key.chars().count() == 1 => KeyCode::Char(key.chars().next().unwrap_or_default()),
+            _ => return Err(format!("Unknown key `<{}>`", name)),
+        };
+        events.push(KeyEvent::new(code, modifiers));
+    }
+    Ok(events)
+}
+
+// Writes the monitors in the requested format and returns the path written.
+pub fn export(options: &CliOptions, monitors: &[Monitor], config: &Configuration) -> io::Result<String> {
+    let path = options.output.clone().unwrap_or(config.monitors_config_path.clone());
+    Monitor::save_config(&path, monitors, options.format, config.minimal_hyprland_config)?;
+    Ok(shellexpand::tilde(&path).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::tests::test_monitors;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_options() {
+        let options = CliOptions::parse(&args(&["--export", "--format", "niri", "--output", "/tmp/out.kdl"]))
+            .expect("Failed to parse");
+        assert!(options.export);
+        assert_eq!(options.format, ConfigFormat::Niri);
+        assert_eq!(options.output, Some("/tmp/out.kdl".to_string()));
+
+        assert_eq!(CliOptions::parse(&[]), Ok(CliOptions::default()));
+        assert!(CliOptions::parse(&args(&["--format", "kde"])).is_err());
+        assert!(CliOptions::parse(&args(&["--output"])).is_err());
+        assert!(CliOptions::parse(&args(&["--verbose"])).expect("Failed to parse").verbose);
+        assert!(CliOptions::parse(&args(&["--quiet"])).is_err());
+        assert!(CliOptions::parse(&args(&["--keys"])).is_err());
+        assert_eq!(
+            CliOptions::parse(&args(&["--monitors-from", "demo.json"])).expect("Failed to parse").monitors_from,
+            Some("demo.json".to_string())
+        );
+        assert!(CliOptions::parse(&args(&["--monitors-from"])).is_err());
+    }
+
+    #[test]
+    fn parse_key_sequence() {
+        assert_eq!(
+            parse_keys("mL<Esc><C-s><A-l><Space>"),
+            Ok(vec![
+                KeyCode::Char('m').into(),
+                KeyCode::Char('L').into(),
+                KeyCode::Esc.into(),
+                KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL),
+                KeyEvent::new(KeyCode::Char('l'), KeyModifiers::ALT),
+                KeyCode::Char(' ').into(),
+            ])
+        );
+        assert!(parse_keys("<Escape>").is_err());
+    }
+
+    #[test]
+    fn export_to_file() {
+        let path = std::env::temp_dir().join("display-tui-export-test.conf");
+        let options = CliOptions::parse(&args(&["--export", "--format", "sway", "--output", path.to_str().unwrap()]))
+            .expect("Failed to parse");
+
+        let written = export(&options, &test_monitors(), &Configuration::default())
+            .expect("Failed to export");
+
+        assert_eq!(written, path.to_str().unwrap());
+        let content = std::fs::read_to_string(&path).expect("Failed to read export");
+        assert_eq!(
+            content,
+            "output Monitor 1 mode 1920x1080@60Hz pos 0 0 scale 1 transform normal\noutput Monitor 2 disable\n"
+        );
+        std::fs::remove_file(path).expect("Failed to remove export");
+    }
+}
@@ -0,0 +1,317 @@
+use crossterm::event::{KeyCode,KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Style,Stylize,Color},
+    symbols::border,
+    text::Line,
+    widgets::{Block,StatefulWidget,Row,Table,Cell,TableState},
+};
+
+use ratatui::layout::Constraint;
+use crate::configuration::Configuration;
+use crate::popup::Popup;
+use crate::utils::{self,MovementKeys,TUIMode};
+use crate::App;
+
+// The config.json fields editable from the TUI
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConfigField {
+    MonitorsConfigPath,
+    ScaleStep,
+    CanvasMargin,
+    CoarseMoveStep,
+    WrapNavigation,
+    MinimalHyprlandConfig,
+    ExactSnap,
+    HyprlandMonitorv2,
+    CloseGaps,
+    RoundRefresh,
+    LiveMove,
+    PulseSelection,
+    ConfirmQuit,
+    CompactList,
+    SplitMonitorFiles,
+    MovementKeys,
+}
+
+impl ConfigField {
+    pub fn table() -> Vec<Self> {
+        vec![
+            ConfigField::MonitorsConfigPath,
+            ConfigField::ScaleStep,
+            ConfigField::CanvasMargin,
+            ConfigField::CoarseMoveStep,
+            ConfigField::WrapNavigation,
+            ConfigField::MinimalHyprlandConfig,
+            ConfigField::ExactSnap,
+            ConfigField::HyprlandMonitorv2,
+            ConfigField::CloseGaps,
+            ConfigField::RoundRefresh,
+            ConfigField::LiveMove,
+            ConfigField::PulseSelection,
+            ConfigField::ConfirmQuit,
+            ConfigField::CompactList,
+            ConfigField::SplitMonitorFiles,
+            ConfigField::MovementKeys,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConfigField::MonitorsConfigPath => "monitors_config_path",
+            ConfigField::ScaleStep => "scale_step",
+            ConfigField::CoarseMoveStep => "coarse_move_step",
+            ConfigField::CanvasMargin => "canvas_margin",
+            ConfigField::WrapNavigation => "wrap_navigation",
+            ConfigField::MinimalHyprlandConfig => "minimal_hyprland_config",
+            ConfigField::ExactSnap => "exact_snap",
+            ConfigField::HyprlandMonitorv2 => "hyprland_monitorv2",
+            ConfigField::CloseGaps => "close_gaps",
+            ConfigField::RoundRefresh => "round_refresh",
+            ConfigField::LiveMove => "live_move",
+            ConfigField::PulseSelection => "pulse_selection",
+            ConfigField::ConfirmQuit => "confirm_quit",
+            ConfigField::CompactList => "compact_list",
+            ConfigField::SplitMonitorFiles => "split_monitor_files",
+            ConfigField::MovementKeys => "movement_keys",
+        }
+    }
+
+    pub fn value(&self, config: &Configuration) -> String {
+        match self {
+            ConfigField::MonitorsConfigPath => config.monitors_config_path.clone(),
+            ConfigField::ScaleStep => config.scale_step.to_string(),
+            ConfigField::CoarseMoveStep => config.coarse_move_step.to_string(),
+            ConfigField::CanvasMargin => config.canvas_margin.to_string(),
+            ConfigField::MovementKeys => format!("{:?}", config.movement_keys).to_lowercase(),
+            _ => self.flag(config).map(|flag| flag.to_string()).unwrap_or_default(),
+        }
+    }
+
+    fn flag(&self, config: &Configuration) -> Option<bool> {
+        match self {
+            ConfigField::WrapNavigation => Some(config.wrap_navigation),
+            ConfigField::MinimalHyprlandConfig => Some(config.minimal_hyprland_config),
+            ConfigField::ExactSnap => Some(config.exact_snap),
+            ConfigField::HyprlandMonitorv2 => Some(config.hyprland_monitorv2),
+            ConfigField::CloseGaps => Some(config.close_gaps),
+            ConfigField::RoundRefresh => Some(config.round_refresh),
+            ConfigField::LiveMove => Some(config.live_move),
+            ConfigField::PulseSelection => Some(config.pulse_selection),
+            ConfigField::ConfirmQuit => Some(config.confirm_quit),
+            ConfigField::CompactList => Some(config.compact_list),
+            ConfigField::SplitMonitorFiles => Some(config.split_monitor_files),
+            _ => None,
+        }
+    }
+
+    fn flag_mut<'a>(&self, config: &'a mut Configuration) -> Option<&'a mut bool> {
+        match self {
+            ConfigField::WrapNavigation => Some(&mut config.wrap_navigation),
+            ConfigField::MinimalHyprlandConfig => Some(&mut config.minimal_hyprland_config),
+            ConfigField::ExactSnap => Some(&mut config.exact_snap),
+            ConfigField::HyprlandMonitorv2 => Some(&mut config.hyprland_monitorv2),
+            ConfigField::CloseGaps => Some(&mut config.close_gaps),
+            ConfigField::RoundRefresh => Some(&mut config.round_refresh),
+            ConfigField::LiveMove => Some(&mut config.live_move),
+            ConfigField::PulseSelection => Some(&mut config.pulse_selection),
+            ConfigField::ConfirmQuit => Some(&mut config.confirm_quit),
+            ConfigField::CompactList => Some(&mut config.compact_list),
+            ConfigField::SplitMonitorFiles => Some(&mut config.split_monitor_files),
+            _ => None,
+        }
+    }
+
+    // Sets a typed value, refusing the ones config.json couldn't hold.
+    pub fn set(&self, config: &mut Configuration, text: &str) -> Result<(), String> {
+        match self {
+            ConfigField::MonitorsConfigPath => {
+                Configuration::validate_monitors_config_path(text)?;
+                config.monitors_config_path = text.to_string();
+            },
+            ConfigField::ScaleStep => {
+                config.scale_step = text.parse::<f32>().ok()
+                    .filter(|step| *step > 0.0)
+                    .ok_or(format!("`{}` is not a positive number", text))?;
+            },
+            ConfigField::CoarseMoveStep => {
+                config.coarse_move_step = text.parse::<i32>().ok()
+                    .filter(|step| *step > 0)
+                    .ok_or(format!("`{}` is not a positive number of pixels", text))?;
+            },
+            ConfigField::CanvasMargin => {
+                config.canvas_margin = text.parse::<f64>().ok()
+                    .filter(|margin| *margin >= 0.0)
+                    .ok_or(format!("`{}` is not a number of pixels", text))?;
+            },
+            _ => return Err(format!("{} is not edited as text", self.name())),
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct ConfigEditor<'a> {
+    pub state: TableState,
+    pub config: &'a Configuration,
+    pub editing_text: Option<&'a str>,
+}
+
+impl<'a> ConfigEditor<'a> {
+    pub fn new(config: &'a Configuration, selected: usize, editing_text: Option<&'a str>) -> Self {
+        ConfigEditor {
+            state: TableState::default()
+                .with_selected(selected),
+            config,
+            editing_text,
+        }
+    }
+
+    pub fn handle_events(app:&mut App, key_event: KeyEvent) {
+        if app.editing_text.is_some() {
+            ConfigEditor::handle_text_events(app, key_event);
+            return;
+        }
+        match key_event.code {
+            KeyCode::Char('k') | KeyCode::Up => ConfigEditor::previous(app),
+            KeyCode::Char('j') | KeyCode::Down => ConfigEditor::next(app),
+            KeyCode::Char(' ') | KeyCode::Enter => ConfigEditor::toggle(app),
+            KeyCode::Esc => app.mode = TUIMode::View,
+            _ => {}
+        }
+    }
+
+    fn handle_text_events(app:&mut App, key_event: KeyEvent) {
+        let Some(text) = app.editing_text.as_mut() else { return };
+        match key_event.code {
+            KeyCode::Char(c) => text.push(c),
+            KeyCode::Backspace => { text.pop(); },
+            KeyCode::Enter => {
+                let text = app.editing_text.take().unwrap_or_default();
+                let field = ConfigField::table()[app.selected_config_field];
+                let mut config = app.config.clone();
+                match field.set(&mut config, &text) {
+                    Ok(_) => ConfigEditor::save(app, config),
+                    Err(e) => app.popup = Some(Popup::new("Config not saved", vec![e])),
+                }
+            },
+            KeyCode::Esc => app.editing_text = None,
+            _ => {}
+        }
+    }
+
+    fn next(app:&mut App) {
+        app.selected_config_field = utils::next_index(app.selected_config_field, ConfigField::table().len(), app.config.wrap_navigation);
+    }
+
+    fn previous(app:&mut App) {
+        app.selected_config_field = utils::previous_index(app.selected_config_field, ConfigField::table().len(), app.config.wrap_navigation);
+    }
+
+    fn toggle(app:&mut App) {
+        let field = ConfigField::table()[app.selected_config_field];
+        let mut config = app.config.clone();
+        if let Some(flag) = field.flag_mut(&mut config) {
+            *flag = !*flag;
+        } else if field == ConfigField::MovementKeys {
+            config.movement_keys = match config.movement_keys {
+                MovementKeys::Both => MovementKeys::Vim,
+                MovementKeys::Vim => MovementKeys::Arrows,
+                MovementKeys::Arrows => MovementKeys::Both,
+            };
+        } else {
+            app.editing_text = Some(field.value(&app.config));
+            return;
+        }
+        ConfigEditor::save(app, config);
+    }
+
+    // The change is kept for this session even if config.json can't be written.
+    fn save(app:&mut App, config: Configuration) {
+        if let Err(e) = config.save() {
+            app.popup = Some(Popup::new("Config not saved", vec![e]));
+        }
+        app.config = config;
+    }
+
+    fn fields_to_rows(&self) -> Vec<Row<'static>> {
+        ConfigField::table()
+            .into_iter()
+            .enumerate()
+            .map(|(index, field)| {
+                let value = match self.editing_text {
+                    Some(text) if self.state.selected() == Some(index) => format!("{}_", text),
+                    _ => field.value(self.config),
+                };
+                Row::new(vec![
+                    Cell::from(field.name()),
+                    Cell::from(value),
+                ])
+            })
+            .collect()
+    }
+
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let title = Line::from(" Config ".bold());
+        let block = Block::bordered()
+            .title(title.white().centered())
+            .border_set(border::THICK)
+            .border_style(Style::default().fg(Color::Yellow));
+
+        let widths = [
+            Constraint::Percentage(50),
+            Constraint::Percentage(50),
+        ];
+
+        let table = Table::new(self.fields_to_rows(),widths)
+            .column_spacing(1)
+            .row_highlight_style(Style::new().yellow())
+            .cell_highlight_style(Style::new().blue())
+            .highlight_symbol("  ")
+            .block(block);
+
+        StatefulWidget::render(
+            table,
+            area,
+            buf,
+            &mut self.state,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::tests::row_text;
+
+    #[test]
+    fn set_text_fields() {
+        let mut config = Configuration::default();
+
+        assert!(ConfigField::ScaleStep.set(&mut config, "0.1").is_ok());
+        assert_eq!(config.scale_step, 0.1);
+        assert!(ConfigField::ScaleStep.set(&mut config, "-1").is_err());
+        assert!(ConfigField::CanvasMargin.set(&mut config, "wide").is_err());
+        assert_eq!(config.canvas_margin, 50.0);
+
+        assert!(ConfigField::MonitorsConfigPath.set(&mut config, "/nonexistent/display-tui/monitors.conf").is_err());
+        let path = std::env::temp_dir().join("monitors.conf");
+        assert!(ConfigField::MonitorsConfigPath.set(&mut config, path.to_str().unwrap()).is_ok());
+        assert_eq!(ConfigField::MonitorsConfigPath.value(&config), path.to_str().unwrap());
+    }
+
+    #[test]
+    fn render_config_editor() {
+        let config = Configuration::default();
+        let mut editor = ConfigEditor::new(&config, 1, Some("0.2"));
+        let mut buf = Buffer::empty(Rect::new(0, 0, 50, 5));
+
+        editor.render(buf.area, &mut buf);
+
+        let lines: Vec<String> = (0..buf.area.height).map(|y| row_text(&buf, y)).collect();
+        assert!(lines[1].contains("monitors_config_path"), "{}", lines[1]);
+        assert!(lines[2].contains("scale_step") && lines[2].contains("0.2_"), "{}", lines[2]);
+        assert!(lines[3].contains("canvas_margin") && lines[3].contains("50"), "{}", lines[3]);
+    }
+}
@@ -1,11 +1,99 @@
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::fs;
 use serde::{Deserialize, Serialize};
-use crate::monitor::{Monitor, Position};
+use crate::monitor::{ModeKeyword, Monitor, Position};
+use crate::logger;
+use crate::utils::{ConfigFormat, LabelAlign, MovementKeys};
 
-#[derive(Debug,Default, Clone, Deserialize)]
+const DEFAULT_MONITORS_CONFIG_PATH: &str = "~/.config/hypr/hyprland/monitors.conf";
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
 pub struct Configuration {
+    // config.json this was read from, written back by the config editor
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+    // monitor_state.json, ~/.config/display-tui/ when unset
+    #[serde(skip)]
+    pub state_path: Option<PathBuf>,
     pub monitors_config_path: String,
+    // j/k past the last item go back to the first, and the other way around
+    pub wrap_navigation: bool,
+    // leave the transform out of unrotated Hyprland monitor lines
+    pub minimal_hyprland_config: bool,
+    // scale change for +/- in Scale mode
+    pub scale_step: f32,
+    // pixels moved by Ctrl+direction in Move mode
+    pub coarse_move_step: i32,
+    // snapping places monitors from the target edge, see Map::snap_move
+    pub exact_snap: bool,
+    // write `monitorv2 { ... }` blocks instead of `monitor = ...` lines
+    pub hyprland_monitorv2: bool,
+    // scale for monitors seen for the first time that report none (or 1.0)
+    pub default_scale: Option<f32>,
+    // space around the monitors on the map, in layout pixels
+    pub canvas_margin: f64,
+    // disabling a monitor moves the ones right of it over, see Monitor::close_gap
+    pub close_gaps: bool,
+    // show 59.951 as ~60 in the resolution list, the config keeps the exact rate
+    pub round_refresh: bool,
+    // apply positions to Hyprland while moving, see App::live_apply
+    pub live_move: bool,
+    // "vim", "arrows" or "both"
+    pub movement_keys: MovementKeys,
+    // connector name -> name shown in the UI, e.g. "DP-3": "Left"
+    pub aliases: HashMap<String, String>,
+    // pulse the selected monitor's border on the map
+    pub pulse_selection: bool,
+    // ask before `q` quits with changes not written by `w`
+    pub confirm_quit: bool,
+    // every file written by `w`, monitors_config_path alone when empty
+    pub output_targets: Vec<OutputTarget>,
+    // "top-left" or "center"
+    pub label_align: LabelAlign,
+    // modes listed first in Resolution mode, as "WxH@refresh"
+    pub pinned_modes: Vec<String>,
+    // one column per monitor row with mode, scale and rotation inline
+    pub compact_list: bool,
+    // one monitors/<name>.conf per monitor, sourced by the Hyprland config
+    pub split_monitor_files: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct OutputTarget {
+    pub format: ConfigFormat,
+    pub path: String,
+}
+
+impl Default for Configuration {
+    fn default() -> Self {
+        Configuration {
+            path: None,
+            state_path: None,
+            monitors_config_path: DEFAULT_MONITORS_CONFIG_PATH.to_string(),
+            wrap_navigation: true,
+            minimal_hyprland_config: false,
+            scale_step: 0.05,
+            coarse_move_step: 50,
+            exact_snap: false,
+            hyprland_monitorv2: false,
+            default_scale: None,
+            canvas_margin: 50.0,
+            close_gaps: false,
+            round_refresh: false,
+            live_move: false,
+            movement_keys: MovementKeys::Both,
+            aliases: HashMap::new(),
+            pulse_selection: false,
+            confirm_quit: true,
+            output_targets: vec![],
+            label_align: LabelAlign::TopLeft,
+            pinned_modes: vec![],
+            compact_list: false,
+            split_monitor_files: false,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,49 +101,209 @@ pub struct MonitorState {
     pub name: String,
     pub position: Option<Position>,
     pub scale: Option<f32>,
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    #[serde(default)]
+    pub resolution: Option<String>,
+    #[serde(default)]
+    pub locked: bool,
+    #[serde(default)]
+    pub default_workspace: Option<String>,
+    #[serde(default)]
+    pub refresh_cap: Option<f32>,
+    #[serde(default)]
+    pub mode_keyword: Option<ModeKeyword>,
+}
+
+impl MonitorState {
+    pub fn from_monitor(monitor: &Monitor) -> Self {
+        MonitorState {
+            name: monitor.name.clone(),
+            position: monitor.position.clone(),
+            scale: monitor.scale,
+            enabled: Some(monitor.enabled),
+            resolution: MonitorState::resolution_label(monitor),
+            locked: monitor.locked,
+            default_workspace: monitor.default_workspace.clone(),
+            refresh_cap: monitor.refresh_cap,
+            mode_keyword: monitor.mode_keyword,
+        }
+    }
+
+    fn resolution_label(monitor: &Monitor) -> Option<String> {
+        let mut mode = monitor.get_current_resolution();
+        if mode.is_none() {
+            mode = monitor.get_prefered_resolution();
+        }
+        mode.map(|m| format!("{}x{}@{}", m.width, m.height, m.refresh))
+    }
+
+    // Human readable changes from this saved state to `monitor`, None when identical.
+    pub fn diff(&self, monitor: &Monitor) -> Option<String> {
+        let current = MonitorState::from_monitor(monitor);
+        let mut changes = vec![];
+        if let Some(enabled) = self.enabled && enabled != monitor.enabled {
+            changes.push(if monitor.enabled { "enabled" } else { "disabled" }.to_string());
+        }
+        if self.resolution.is_some() && self.resolution != current.resolution {
+            changes.push(format!(
+                "resolution {} -> {}",
+                self.resolution.clone().unwrap_or_default(),
+                current.resolution.unwrap_or("N/A".to_string())
+            ));
+        }
+        if self.scale != monitor.scale {
+            changes.push(format!(
+                "scale {:?} -> {:?}",
+                self.scale.unwrap_or(1.0),
+                monitor.scale.unwrap_or(1.0)
+            ));
+        }
+        if self.position != monitor.position {
+            let position = |p: &Option<Position>| match p {
+                Some(p) => format!("({},{})", p.x, p.y),
+                None => "N/A".to_string(),
+            };
+            changes.push(format!("moved {} -> {}", position(&self.position), position(&monitor.position)));
+        }
+        if changes.is_empty() {
+            None
+        } else {
+            Some(format!("{}: {}", monitor.name, changes.join(", ")))
+        }
+    }
 }
 impl Configuration {
+    pub fn hyprland_format(&self) -> ConfigFormat {
+        if self.hyprland_monitorv2 { ConfigFormat::HyprlandV2 } else { ConfigFormat::Hyprland }
+    }
+
+    pub fn write_targets(&self) -> Vec<OutputTarget> {
+        if !self.output_targets.is_empty() {
+            return self.output_targets.clone();
+        }
+        vec![OutputTarget {
+            format: self.hyprland_format(),
+            path: self.monitors_config_path.clone(),
+        }]
+    }
+
+    // $DISPLAY_TUI_CONFIG if set, with ~ and variables expanded, to keep
+    // several profiles side by side. ~/.config/display-tui/config.json otherwise.
+    fn config_path() -> PathBuf {
+        Configuration::config_path_from(std::env::var("DISPLAY_TUI_CONFIG").ok())
+    }
+
+    fn config_path_from(env_path: Option<String>) -> PathBuf {
+        match env_path {
+            Some(path) if !path.is_empty() => {
+                let expanded = shellexpand::full(&path).map(|p| p.to_string()).unwrap_or_else(|e| {
+                    logger::warn(&format!("Failed to expand DISPLAY_TUI_CONFIG: {}", e));
+                    path.clone()
+                });
+                PathBuf::from(expanded)
+            },
+            _ => dirs::home_dir()
+                .map(|p| p.join(".config/display-tui/config.json"))
+                .unwrap_or_else(|| Path::new("~/.config/display-tui/config.json").to_path_buf()),
+        }
+    }
+
     pub fn get() -> Self {
-        let config_json_path = dirs::home_dir()
-             .map(|p| p.join(".config/display-tui/config.json"))
-             .unwrap_or_else(|| Path::new("~/.config/display-tui/config.json").to_path_buf());
-        match !config_json_path.exists() {
+        let config_json_path = Configuration::config_path();
+        let config = match !config_json_path.exists() {
             true => {
                 Configuration::create_default_config(&config_json_path)
             },
             false => {
-                Configuration::load_config()
+                Configuration::load_config(&config_json_path).unwrap_or_else(|e| {
+                    logger::warn(&format!("{}, using defaults", e));
+                    Configuration::default()
+                })
             }
+        };
+        Configuration { path: Some(config_json_path), ..config }
+    }
+
+    // Writes every field back to `path`, nothing to do without one.
+    pub fn save(&self) -> Result<(), String> {
+        let Some(path) = &self.path else { return Ok(()) };
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize the config: {}", e))?;
+        fs::write(path, json)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    // The directory must exist already, the file itself is created on save.
+    pub fn validate_monitors_config_path(path: &str) -> Result<(), String> {
+        let expanded = shellexpand::full(path)
+            .map_err(|e| format!("Failed to expand `{}`: {}", path, e))?;
+        let expanded = Path::new(expanded.as_ref());
+        if expanded.is_dir() {
+            return Err(format!("{} is a directory", expanded.display()));
         }
+        match expanded.parent() {
+            Some(parent) if parent.as_os_str().is_empty() || parent.is_dir() => Ok(()),
+            _ => Err(format!("No directory for {}", expanded.display())),
+        }
+    }
+
+    fn monitor_state_path(&self) -> PathBuf {
+        self.state_path.clone().unwrap_or_else(Configuration::default_state_path)
     }
 
-    pub fn load_monitor_state() -> Option<Vec<MonitorState>> {
-        let state_path = dirs::home_dir()
+    #[cfg(not(test))]
+    fn default_state_path() -> PathBuf {
+        dirs::home_dir()
             .map(|p| p.join(".config/display-tui/monitor_state.json"))
-            .unwrap_or_else(|| Path::new("~/.config/display-tui/monitor_state.json").to_path_buf());
-        
+            .unwrap_or_else(|| Path::new("~/.config/display-tui/monitor_state.json").to_path_buf())
+    }
+
+    // Tests without a state_path must not overwrite the user's state.
+    #[cfg(test)]
+    fn default_state_path() -> PathBuf {
+        std::env::temp_dir().join("display-tui-test").join("monitor_state.json")
+    }
+
+    pub fn load_monitor_state(&self) -> Option<Vec<MonitorState>> {
+        Configuration::load_monitor_state_from(&self.monitor_state_path())
+    }
+
+    // A state file that doesn't parse is moved aside to `<name>.corrupt`
+    // so it isn't overwritten, and the app starts from the wlr-randr values.
+    fn load_monitor_state_from(state_path: &Path) -> Option<Vec<MonitorState>> {
         if !state_path.exists() {
             return None;
         }
 
-        let content = fs::read_to_string(&state_path).ok()?;
-        serde_json::from_str(&content).ok()
+        let content = fs::read_to_string(state_path).ok()?;
+        match serde_json::from_str(&content) {
+            Ok(state) => Some(state),
+            Err(e) => {
+                let mut corrupt_path = state_path.as_os_str().to_owned();
+                corrupt_path.push(".corrupt");
+                let corrupt_path = PathBuf::from(corrupt_path);
+                match fs::rename(state_path, &corrupt_path) {
+                    Ok(_) => logger::warn(&format!(
+                        "Invalid monitor state ({}), moved to {}", e, corrupt_path.display()
+                    )),
+                    Err(rename_error) => logger::warn(&format!(
+                        "Invalid monitor state ({}), could not move it aside: {}", e, rename_error
+                    )),
+                }
+                None
+            }
+        }
     }
 
-    pub fn save_monitor_state(monitors: &Vec<Monitor>) -> std::io::Result<()> {
-        let state_path = dirs::home_dir()
-            .map(|p| p.join(".config/display-tui/monitor_state.json"))
-            .unwrap_or_else(|| Path::new("~/.config/display-tui/monitor_state.json").to_path_buf());
+    pub fn save_monitor_state(&self, monitors: &[Monitor]) -> std::io::Result<()> {
+        let state_path = self.monitor_state_path();
         
         fs::create_dir_all(state_path.parent().unwrap())?;
         
         let state: Vec<MonitorState> = monitors
             .iter()
-            .map(|m| MonitorState {
-                name: m.name.clone(),
-                position: m.position.clone(),
-                scale: m.scale,
-            })
+            .map(MonitorState::from_monitor)
             .collect();
         
         let json = serde_json::to_string_pretty(&state)
@@ -65,27 +313,54 @@ impl Configuration {
         Ok(())
     }
 
+    pub fn diff_monitor_state(saved: &[MonitorState], monitors: &[Monitor]) -> Vec<String> {
+        monitors
+            .iter()
+            .filter_map(|monitor| match saved.iter().find(|s| s.name == monitor.name) {
+                Some(state) => state.diff(monitor),
+                None => Some(format!("{}: not in saved state", monitor.name)),
+            })
+            .collect()
+    }
+
     fn create_default_config(config_json_path: &PathBuf) -> Self {
-        let default_monitors_config_path = "~/.config/hypr/hyprland/monitors.conf";
+        let default_monitors_config_path = DEFAULT_MONITORS_CONFIG_PATH;
         let default_config =format!("{{\n  \"monitors_config_path\": \"{}\"\n}}", default_monitors_config_path);
         fs::create_dir_all(config_json_path.parent().unwrap()).expect("Failed to create config directory");
         fs::write(config_json_path, default_config).expect("Failed to write default config file");
         Configuration {
             monitors_config_path: default_monitors_config_path.to_string(),
+            ..Configuration::default()
         } 
     }
-    fn load_config() -> Self {
-        let config_json_path = dirs::home_dir()
-            .map(|p| p.join(".config/display-tui/config.json"))
-            .unwrap_or_else(|| Path::new("~/.config/display-tui/config.json").to_path_buf());
-        
+    fn load_config(config_json_path: &Path) -> Result<Self, String> {
         let config_content = fs::read_to_string(config_json_path)
-            .expect("Failed to read config file");
-        
-        let config: Configuration = serde_json::from_str(&config_content)
-            .expect("Failed to parse config file");
+            .map_err(|e| format!("Failed to read {}: {}", config_json_path.display(), e))?;
         
-        config
+        Configuration::parse(&config_content)
+    }
+
+    // Unknown keys are ignored and missing keys fall back to their default.
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let value: serde_json::Value = serde_json::from_str(content)
+            .map_err(|e| format!("Invalid JSON in config.json: {}", e))?;
+
+        serde_json::from_value(value.clone()).map_err(|e| {
+            // retry key by key to point at the offending one
+            let offending_key = value.as_object().and_then(|object| {
+                object.iter().find_map(|(key, field)| {
+                    let mut single = serde_json::Map::new();
+                    single.insert(key.clone(), field.clone());
+                    serde_json::from_value::<Configuration>(serde_json::Value::Object(single))
+                        .err()
+                        .map(|_| key.clone())
+                })
+            });
+            match offending_key {
+                Some(key) => format!("Invalid value for `{}` in config.json: {}", key, e),
+                None => format!("Invalid config.json: {}", e),
+            }
+        })
     }
 }
 
@@ -93,6 +368,7 @@ impl Configuration {
 mod tests {
     use super::*;
     use crate::monitor::{Monitor, Position};
+    use crate::test_utils::tests::test_config;
 
     #[test]
     fn test_save_and_load_monitor_state() {
@@ -114,11 +390,13 @@ mod tests {
             },
         ];
 
+        let config = test_config("save-and-load-state");
+
         // Save
-        Configuration::save_monitor_state(&monitors).expect("Failed to save");
+        config.save_monitor_state(&monitors).expect("Failed to save");
 
         // Load
-        let loaded = Configuration::load_monitor_state().expect("Failed to load");
+        let loaded = config.load_monitor_state().expect("Failed to load");
 
         // Verify
         assert_eq!(loaded.len(), 2);
@@ -130,4 +408,161 @@ mod tests {
         assert_eq!(loaded[1].position, Some(Position { x: 300, y: 400 }));
         assert_eq!(loaded[1].scale, Some(1.0));
     }
+
+    #[test]
+    fn test_monitor_state_mode_keyword() {
+        let monitor = Monitor {
+            name: "DP-1".to_string(),
+            mode_keyword: Some(ModeKeyword::HighRr),
+            ..Default::default()
+        };
+
+        let json = serde_json::to_string(&MonitorState::from_monitor(&monitor)).unwrap();
+        assert!(json.contains(r#""mode_keyword":"highrr""#), "{}", json);
+        let state: MonitorState = serde_json::from_str(&json).unwrap();
+        assert_eq!(state.mode_keyword, Some(ModeKeyword::HighRr));
+
+        // states saved before the option load without one
+        let state: MonitorState = serde_json::from_str(r#"{"name": "DP-1", "position": null, "scale": null}"#).unwrap();
+        assert_eq!(state.mode_keyword, None);
+    }
+
+    #[test]
+    fn test_load_corrupt_monitor_state() {
+        let dir = std::env::temp_dir().join("display-tui-corrupt-state-test");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("Failed to create dir");
+        let state_path = dir.join("monitor_state.json");
+        fs::write(&state_path, "[{\"name\": \"DP-1\", \"position\": ").expect("Failed to write state");
+
+        assert!(Configuration::load_monitor_state_from(&state_path).is_none());
+
+        assert!(!state_path.exists());
+        let corrupt = fs::read_to_string(dir.join("monitor_state.json.corrupt")).expect("Not quarantined");
+        assert_eq!(corrupt, "[{\"name\": \"DP-1\", \"position\": ");
+        // next start is a clean first launch
+        assert!(Configuration::load_monitor_state_from(&state_path).is_none());
+    }
+
+    #[test]
+    fn test_default_state_path_in_tests() {
+        // App { ..Default::default() } in tests saves here, not in ~/.config
+        let path = Configuration::default().monitor_state_path();
+        assert!(path.starts_with(std::env::temp_dir()), "{}", path.display());
+    }
+
+    #[test]
+    fn test_diff_monitor_state() {
+        let monitors = vec![
+            Monitor {
+                name: "HDMI-A-1".to_string(),
+                position: Some(Position { x: 100, y: 200 }),
+                scale: Some(1.5),
+                enabled: true,
+                ..Default::default()
+            },
+            Monitor {
+                name: "DP-1".to_string(),
+                position: Some(Position { x: 1920, y: 0 }),
+                scale: Some(1.5),
+                enabled: false,
+                ..Default::default()
+            },
+            Monitor {
+                name: "DP-2".to_string(),
+                ..Default::default()
+            },
+        ];
+        let mut saved: Vec<MonitorState> = monitors.iter().map(MonitorState::from_monitor).collect();
+        saved.pop();
+        saved[1].position = Some(Position { x: 0, y: 0 });
+        saved[1].scale = Some(1.0);
+        saved[1].enabled = Some(true);
+
+        assert_eq!(
+            Configuration::diff_monitor_state(&saved, &monitors),
+            vec![
+                "DP-1: disabled, scale 1.0 -> 1.5, moved (0,0) -> (1920,0)",
+                "DP-2: not in saved state",
+            ]
+        );
+
+        // states saved before enabled/resolution were recorded
+        saved[1].enabled = None;
+        saved[1].resolution = None;
+        assert_eq!(
+            Configuration::diff_monitor_state(&saved[1..], &monitors[1..2]),
+            vec!["DP-1: scale 1.0 -> 1.5, moved (0,0) -> (1920,0)"]
+        );
+    }
+
+    #[test]
+    fn test_save_config_round_trip() {
+        let path = std::env::temp_dir().join("display-tui-save-config-test.json");
+        let monitors_path = std::env::temp_dir().join("monitors.conf");
+        let mut config = Configuration {
+            path: Some(path.clone()),
+            ..Default::default()
+        };
+        assert!(Configuration::validate_monitors_config_path(monitors_path.to_str().unwrap()).is_ok());
+        assert!(Configuration::validate_monitors_config_path("/nonexistent/display-tui/monitors.conf").is_err());
+        config.monitors_config_path = monitors_path.to_str().unwrap().to_string();
+        config.movement_keys = MovementKeys::Arrows;
+
+        config.save().expect("Failed to save config");
+        let loaded = Configuration::load_config(&path).expect("Failed to load config");
+
+        assert_eq!(loaded.monitors_config_path, config.monitors_config_path);
+        assert_eq!(loaded.movement_keys, MovementKeys::Arrows);
+        assert_eq!(loaded.scale_step, config.scale_step);
+    }
+
+    #[test]
+    fn test_config_from_env() {
+        let dir = std::env::temp_dir().join("display-tui-env-config-test");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("Failed to create dir");
+        let work = dir.join("work.json");
+        fs::write(&work, r#"{"monitors_config_path": "~/work.conf", "scale_step": 0.1}"#)
+            .expect("Failed to write config");
+
+        let path = Configuration::config_path_from(Some(work.to_str().unwrap().to_string()));
+        assert_eq!(path, work);
+        let config = Configuration::load_config(&path).expect("Failed to load");
+        assert_eq!(config.monitors_config_path, "~/work.conf");
+        assert_eq!(config.scale_step, 0.1);
+
+        // ~ is expanded, unset or empty falls back to the default
+        let home = dirs::home_dir().expect("No home");
+        assert_eq!(Configuration::config_path_from(Some("~/work.json".to_string())), home.join("work.json"));
+        let default = home.join(".config/display-tui/config.json");
+        assert_eq!(Configuration::config_path_from(None), default);
+        assert_eq!(Configuration::config_path_from(Some(String::new())), default);
+    }
+
+    #[test]
+    fn test_parse_config() {
+        let config = Configuration::parse(r#"{"monitors_config_path": "~/monitors.conf", "unknown": 1}"#)
+            .expect("Failed to parse");
+        assert_eq!(config.monitors_config_path, "~/monitors.conf");
+
+        let config = Configuration::parse("{}").expect("Failed to parse");
+        assert_eq!(config.monitors_config_path, DEFAULT_MONITORS_CONFIG_PATH);
+        assert_eq!(config.movement_keys, MovementKeys::Both);
+        assert!(config.wrap_navigation);
+
+        let config = Configuration::parse(r#"{"wrap_navigation": false}"#).expect("Failed to parse");
+        assert!(!config.wrap_navigation);
+    }
+
+    #[test]
+    fn test_parse_malformed_config() {
+        let error = Configuration::parse("{\n  \"monitors_config_path\": \"~/monitors.conf\",\n}")
+            .unwrap_err();
+        assert!(error.contains("line 3 column 1"), "{}", error);
+
+        let error = Configuration::parse(r#"{"monitors_config_path": 5}"#).unwrap_err();
+        assert!(error.contains("`monitors_config_path`"), "{}", error);
+        assert!(error.contains("expected a string"), "{}", error);
+    }
 }
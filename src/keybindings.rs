@@ -0,0 +1,212 @@
+use ratatui::{
+    style::Stylize,
+    text::{Line, Span},
+};
+
+use crate::utils::TUIMode;
+
+// One entry per action, both the footer and the `?` help are generated from
+// this table so they list the same keys.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Binding {
+    // None for the keys working in every mode
+    pub mode: Option<TUIMode>,
+    pub action: &'static str,
+    pub key: &'static str,
+    // shown in the footer, the help lists every binding
+    pub footer: bool,
+}
+
+impl Binding {
+    fn new(mode: Option<TUIMode>, action: &'static str, key: &'static str, footer: bool) -> Self {
+        Binding { mode, action, key, footer }
+    }
+}
+
+pub fn defaults() -> Vec<Binding> {
+    use TUIMode::*;
+    let global = None;
+    vec![
+        Binding::new(Some(View), "Up", "<k>", true),
+        Binding::new(Some(View), "Down", "<j>", true),
+        Binding::new(Some(View), "Move", "<m>", true),
+        Binding::new(Some(View), "Resolution", "<r>", true),
+        Binding::new(Some(View), "Scale", "<s>", true),
+        Binding::new(Some(View), "Rotate", "<o>", true),
+        Binding::new(Some(View), "Disable", "<d>", true),
+        Binding::new(Some(View), "Enable", "<e>", true),
+        Binding::new(Some(View), "Keep only selected", "<O>", false),
+        Binding::new(Some(View), "Enable all", "<E>", false),
+        Binding::new(Some(View), "Settings", "<g>", false),
+        Binding::new(Some(View), "Arrange", "<a>", false),
+        Binding::new(Some(View), "Config", "<C>", false),
+        Binding::new(Some(View), "Copy settings", "<c>", false),
+        Binding::new(Some(View), "Scale all", "<S>", false),
+        Binding::new(Some(View), "Next scale", "<z>", false),
+        Binding::new(Some(View), "Lock", "<L>", false),
+        Binding::new(Some(View), "Preferred / previous mode", "<p>", false),
+        Binding::new(Some(View), "Identify", "<I>", false),
+        Binding::new(Some(View), "Changes", "<D>", false),
+        Binding::new(Some(View), "Reconcile modes", "<R>", false),
+        Binding::new(Some(View), "Validate layout", "<!>", false),
+        Binding::new(Some(View), "Copy config line", "<y>", false),
+        Binding::new(Some(View), "Presentation / restore", "<P>", false),
+        Binding::new(Some(View), "Align tops", "<T>", false),
+        Binding::new(Some(View), "Align bottoms", "<B>", false),
+        Binding::new(Some(View), "Select neighbour", "<Alt>+<hjkl>", false),
+        Binding::new(Some(View), "Select primary", "<0>", false),
+        Binding::new(Some(View), "Pan map", "<Ctrl>+<hjkl>", false),
+
+        Binding::new(Some(Resolution), "Up", "<k>", true),
+        Binding::new(Some(Resolution), "Down", "<j>", true),
+        Binding::new(Some(Resolution), "Select", "<Space>", true),
+        Binding::new(Some(Resolution), "Max Refresh", "<M>", true),
+        Binding::new(Some(Resolution), "Pin / unpin", "<p>", false),
+        Binding::new(Some(Resolution), "Page", "<PgUp/PgDn>", false),
+        Binding::new(Some(Resolution), "Quit Resolution Mode", "<Esc>", true),
+
+        Binding::new(Some(Move), "Fast", "<MAJ>+<*>", true),
+        Binding::new(Some(Move), "Coarse", "<Ctrl>+<hjkl>", false),
+        Binding::new(Some(Move), "Up", "<k>", true),
+        Binding::new(Some(Move), "Down", "<j>", true),
+        Binding::new(Some(Move), "Left", "<h>", true),
+        Binding::new(Some(Move), "Right", "<l>", true),
+        Binding::new(Some(Move), "Snap nearest", "<a>", false),
+        Binding::new(Some(Move), "Stick to neighbour", "<Alt>+<hjkl>", false),
+        Binding::new(Some(Move), "Keep live move", "<Enter>", false),
+        Binding::new(Some(Move), "Quit Move Mode", "<Esc>", true),
+
+        Binding::new(Some(Scale), "Up", "<k>", true),
+        Binding::new(Some(Scale), "Down", "<j>", true),
+        Binding::new(Some(Scale), "Select", "<Space>", true),
+        Binding::new(Some(Scale), "Adjust", "<+/->", true),
+        Binding::new(Some(Scale), "Page", "<PgUp/PgDn>", false),
+        Binding::new(Some(Scale), "Quit Scale Mode", "<Esc>", true),
+
+        Binding::new(Some(Settings), "Up", "<k>", true),
+        Binding::new(Some(Settings), "Down", "<j>", true),
+        Binding::new(Some(Settings), "Toggle", "<Space>", true),
+        Binding::new(Some(Settings), "Quit Settings Mode", "<Esc>", true),
+
+        Binding::new(Some(Arrange), "Up", "<k>", true),
+        Binding::new(Some(Arrange), "Down", "<j>", true),
+        Binding::new(Some(Arrange), "Apply", "<Space>", true),
+        Binding::new(Some(Arrange), "Quit Arrange Mode", "<Esc>", true),
+
+        Binding::new(Some(Config), "Up", "<k>", true),
+        Binding::new(Some(Config), "Down", "<j>", true),
+        Binding::new(Some(Config), "Toggle / edit", "<Space>", true),
+        Binding::new(Some(Config), "Quit Config Mode", "<Esc>", true),
+
+        Binding::new(global, "Save", "<w>", true),
+        Binding::new(global, "Save selected only", "<W>", false),
+        Binding::new(global, "Save and apply", "<Ctrl>+<s>", false),
+        Binding::new(global, "Save, apply and quit", "<Z>", false),
+        Binding::new(global, "Save, reload and verify", "<V>", false),
+        Binding::new(global, "Layout", "<v>", false),
+        Binding::new(global, "Grid", "<G>", false),
+        Binding::new(global, "Native footprint", "<F>", false),
+        Binding::new(global, "Fit map", "<f>", false),
+        Binding::new(global, "Native/logical positions and nudges", "<n>", false),
+        Binding::new(global, "Dismiss cursor note", "<X>", false),
+        Binding::new(global, "Help", "<?>", false),
+        Binding::new(global, "Quit", "<q>", true),
+    ]
+}
+
+fn mode_name(mode: Option<TUIMode>) -> &'static str {
+    match mode {
+        None => "Global",
+        Some(TUIMode::View) => "View",
+        Some(TUIMode::Move) => "Move",
+        Some(TUIMode::Resolution) => "Resolution",
+        Some(TUIMode::Scale) => "Scale",
+        Some(TUIMode::Settings) => "Settings",
+        Some(TUIMode::Arrange) => "Arrange",
+        Some(TUIMode::Config) => "Config",
+    }
+}
+
+fn spans(binding: &Binding) -> [Span<'static>; 2] {
+    [
+        format!(" {} ", binding.action).white(),
+        format!("{} ", binding.key).blue().bold(),
+    ]
+}
+
+// Footer of the monitor list: the mode's keys then the global ones,
+// leaving out the `hidden` actions.
+pub fn footer(bindings: &[Binding], mode: TUIMode, hidden: &[&str]) -> Line<'static> {
+    let in_mode = bindings.iter().filter(|b| b.mode == Some(mode));
+    let global = bindings.iter().filter(|b| b.mode.is_none());
+    Line::from(
+        in_mode.chain(global)
+            .filter(|b| b.footer && !hidden.contains(&b.action))
+            .flat_map(spans)
+            .collect::<Vec<_>>()
+    )
+}
+
+// Every binding, grouped by mode in the order of the table.
+pub fn help_lines(bindings: &[Binding]) -> Vec<Line<'static>> {
+    let mut modes: Vec<Option<TUIMode>> = vec![];
+    for binding in bindings {
+        if !modes.contains(&binding.mode) {
+            modes.push(binding.mode);
+        }
+    }
+
+    let mut lines = vec![];
+    for mode in modes {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(mode_name(mode).bold()));
+        for binding in bindings.iter().filter(|b| b.mode == mode) {
+            lines.push(Line::from(Vec::from(spans(binding))));
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(line: &Line) -> String {
+        line.to_string()
+    }
+
+    #[test]
+    fn footer_view_mode() {
+        let footer = footer(&defaults(), TUIMode::View, &["Enable"]);
+        assert_eq!(
+            text(&footer),
+            " Up <k>  Down <j>  Move <m>  Resolution <r>  Scale <s>  Rotate <o>  Disable <d>  Save <w>  Quit <q> "
+        );
+    }
+
+    #[test]
+    fn help_lines_grouped_by_mode() {
+        let lines: Vec<String> = help_lines(&defaults()).iter().map(text).collect();
+        let view = lines.iter().position(|l| l == "View").expect("No View group");
+        let global = lines.iter().position(|l| l == "Global").expect("No Global group");
+        assert_eq!(lines[view + 1], " Up <k> ");
+        assert!(lines[global..].contains(&" Help <?> ".to_string()), "{:?}", lines);
+        assert_eq!(lines.iter().filter(|l| l.as_str() == "Move").count(), 1);
+    }
+
+    #[test]
+    fn help_lines_remapped_binding() {
+        let mut bindings = defaults();
+        let binding = bindings.iter_mut()
+            .find(|b| b.mode == Some(TUIMode::View) && b.action == "Move")
+            .expect("No Move binding");
+        binding.key = "<M>";
+
+        let lines: Vec<String> = help_lines(&bindings).iter().map(text).collect();
+        assert!(lines.contains(&" Move <M> ".to_string()), "{:?}", lines);
+        assert!(!lines.contains(&" Move <m> ".to_string()), "{:?}", lines);
+        assert!(text(&footer(&bindings, TUIMode::View, &[])).contains(" Move <M> "));
+    }
+}
@@ -1,4 +1,4 @@
-use crossterm::event::{KeyCode,KeyEvent};
+use crossterm::event::{KeyCode,KeyEvent,KeyModifiers};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
@@ -7,11 +7,16 @@ use ratatui::{
     text::Line,
     widgets::{Cell,Block,StatefulWidget,Row,Table,TableState},
 };
-use crate::monitor::{Monitor,Position};
+use crate::monitor::{Monitor, Position, Relation};
 
 use ratatui::layout::Constraint;
-use crate::utils::TUIMode;
+use crate::utils::{self,Coordinates,TUIMode};
+use crate::configuration::Configuration;
+use crate::popup::Popup;
+use crate::logger;
+use crate::keybindings;
 use crate::rotation::Rotation;
+use crate::scale::Scale;
 use crate::App;
 
 #[derive(Debug)]
@@ -20,6 +25,11 @@ pub struct MonitorList<'a> {
     pub selected_row: Option<usize>,
     pub state: TableState,
     pub monitors:&'a Vec<Monitor>,
+    pub coordinates: Coordinates,
+    // right side of the footer
+    pub status: Option<String>,
+    // one summary column instead of the table, see Monitor::list_row_string
+    pub compact: bool,
 }
 
 
@@ -31,19 +41,81 @@ impl<'a> MonitorList<'a> {
             state: TableState::default()
                 .with_selected(selected_row),
             monitors,
+            coordinates: Coordinates::default(),
+            status: None,
+            compact: false,
         }
     }
 
+    pub fn with_compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+
+    pub fn with_status(mut self, status: String) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    pub fn with_coordinates(mut self, coordinates: Coordinates) -> Self {
+        self.coordinates = coordinates;
+        self
+    }
+
     pub fn handle_events(app:&mut App, key_event: KeyEvent) {
+        if key_event.modifiers.contains(KeyModifiers::ALT) {
+            match key_event.code {
+                KeyCode::Char('k') | KeyCode::Up => MonitorList::select_neighbor(app, Relation::Above),
+                KeyCode::Char('j') | KeyCode::Down => MonitorList::select_neighbor(app, Relation::Below),
+                KeyCode::Char('h') | KeyCode::Left => MonitorList::select_neighbor(app, Relation::LeftOf),
+                KeyCode::Char('l') | KeyCode::Right => MonitorList::select_neighbor(app, Relation::RightOf),
+                _ => {}
+            }
+            return;
+        }
+        if key_event.modifiers.contains(KeyModifiers::CONTROL) {
+            match key_event.code {
+                KeyCode::Char('k') | KeyCode::Up => app.viewport.pan(0.0, -1.0),
+                KeyCode::Char('j') | KeyCode::Down => app.viewport.pan(0.0, 1.0),
+                KeyCode::Char('h') | KeyCode::Left => app.viewport.pan(-1.0, 0.0),
+                KeyCode::Char('l') | KeyCode::Right => app.viewport.pan(1.0, 0.0),
+                _ => {}
+            }
+            return;
+        }
         match key_event.code {
             KeyCode::Char('k') | KeyCode::Up => MonitorList::previous_monitor(app),
             KeyCode::Char('j') | KeyCode::Down => MonitorList::next_monitor(app),
             KeyCode::Char('e')=> MonitorList::enable_monitor(app),
             KeyCode::Char('d')=> MonitorList::disable_monitor(app),
-            KeyCode::Char('m') => MonitorList::change_mode(app,TUIMode::Move),
+            KeyCode::Char('O')=> MonitorList::disable_all_but_selected(app),
+            KeyCode::Char('E')=> MonitorList::enable_all(app),
+            KeyCode::Char('m') => {
+                if app.config.live_move {
+                    app.move_snapshot = Some(app.monitors.clone());
+                }
+                MonitorList::change_mode(app,TUIMode::Move)
+            },
             KeyCode::Char('r') => MonitorList::change_mode(app,TUIMode::Resolution),
             KeyCode::Char('s') => MonitorList::change_mode(app,TUIMode::Scale),
+            KeyCode::Char('g') => MonitorList::change_mode(app,TUIMode::Settings),
+            KeyCode::Char('a') => MonitorList::change_mode(app,TUIMode::Arrange),
+            KeyCode::Char('C') => MonitorList::change_mode(app,TUIMode::Config),
             KeyCode::Char('o') => MonitorList::cycle_rotation(app),
+            KeyCode::Char('c') => MonitorList::duplicate_settings(app),
+            KeyCode::Char('S') => MonitorList::scale_all(app),
+            KeyCode::Char('z') => MonitorList::cycle_scale(app),
+            KeyCode::Char('L') => MonitorList::toggle_lock(app),
+            KeyCode::Char('0') => MonitorList::select_primary(app),
+            KeyCode::Char('p') => MonitorList::toggle_preferred_mode(app),
+            KeyCode::Char('I') => Monitor::identify(&app.runner, &app.monitors),
+            KeyCode::Char('D') => MonitorList::show_diff(app),
+            KeyCode::Char('R') => MonitorList::reconcile(app),
+            KeyCode::Char('!') => MonitorList::validate(app),
+            KeyCode::Char('y') => MonitorList::copy_config_line(app),
+            KeyCode::Char('P') => MonitorList::toggle_presentation(app),
+            KeyCode::Char('T') => Monitor::align_edges(&mut app.monitors, app.selected_monitor, false),
+            KeyCode::Char('B') => Monitor::align_edges(&mut app.monitors, app.selected_monitor, true),
             _ => {}
         }
     }
@@ -54,93 +126,268 @@ impl<'a> MonitorList<'a> {
         monitor.transform = Some(next_rotation.to_transform().to_string());
     }
 
+    fn select_neighbor(app:&mut App, relation: Relation) {
+        if let Some(index) = Monitor::neighbor(&app.monitors, app.selected_monitor, relation) {
+            app.selected_monitor = index;
+        }
+    }
+
+    fn select_primary(app:&mut App) {
+        if let Some(index) = Monitor::primary_index(&app.monitors) {
+            app.selected_monitor = index;
+        }
+    }
+
+    fn toggle_lock(app:&mut App) {
+        let monitor = &mut app.monitors[app.selected_monitor];
+        monitor.locked = !monitor.locked;
+    }
+
+    fn toggle_preferred_mode(app:&mut App) {
+        let monitor = &mut app.monitors[app.selected_monitor];
+        if !monitor.locked {
+            monitor.toggle_preferred_mode();
+        }
+    }
+
+    // Locked monitors keep their settings, like with scale_all.
+    fn duplicate_settings(app:&mut App) {
+        let source = app.monitors[app.selected_monitor].clone();
+        let mut skipped = vec![];
+        for (i, monitor) in app.monitors.iter_mut().enumerate() {
+            if i == app.selected_monitor {
+                continue;
+            }
+            if monitor.locked {
+                skipped.push(format!("{}: locked", monitor.name));
+            } else if !monitor.copy_settings_from(&source) {
+                skipped.push(format!("{}: no mode matching {}'s", monitor.name, source.name));
+            }
+        }
+        if !skipped.is_empty() {
+            logger::warn(&format!("Skipped copying settings to: {}", skipped.join(", ")));
+            let mut lines = vec![format!("Not copied from {}:", source.name)];
+            lines.extend(skipped);
+            app.popup = Some(Popup::new("Copy settings", lines));
+        }
+    }
+
+    // Applies the selected monitor's scale to every enabled, unlocked monitor.
+    fn scale_all(app:&mut App) {
+        let scale = app.monitors[app.selected_monitor].scale;
+        let mut fractional = vec![];
+        for monitor in app.monitors.iter_mut() {
+            if !monitor.enabled || monitor.locked {
+                continue;
+            }
+            monitor.scale = scale;
+            if !monitor.has_integer_logical_size() {
+                let (_, _, w, h) = monitor.get_geometry();
+                fractional.push(format!("{}: {:.2}x{:.2}", monitor.name, w, h));
+            }
+        }
+        if !fractional.is_empty() {
+            let mut lines = vec!["Non-integer logical size at this scale:".to_string()];
+            lines.extend(fractional);
+            app.popup = Some(Popup::new("Scale", lines));
+        }
+    }
+
+    // Steps through the Scale mode values without entering it.
+    fn cycle_scale(app:&mut App) {
+        let monitor = &mut app.monitors[app.selected_monitor];
+        if !monitor.enabled || monitor.locked {
+            return;
+        }
+        monitor.scale = Some(Scale::next_preset(monitor.scale.unwrap_or(1.0)));
+    }
+
+    fn show_diff(app:&mut App) {
+        let saved = app.config.load_monitor_state().unwrap_or_default();
+        let mut lines = Configuration::diff_monitor_state(&saved, &app.monitors);
+        if lines.is_empty() {
+            lines.push("No changes since last save".to_string());
+        }
+        app.popup = Some(Popup::new("Changes", lines));
+    }
+
+    // Runs wlr-randr again, after the compositor rejected a mode, to show
+    // the modes actually running.
+    fn reconcile(app:&mut App) {
+        let stdout = match app.runner.run("wlr-randr", &["--json".to_string()]) {
+            Ok(stdout) => stdout,
+            Err(e) => {
+                logger::error(&format!("Failed to run wlr-randr: {}", e));
+                app.popup = Some(Popup::new("Reconcile failed", vec![format!("wlr-randr: {}", e)]));
+                return;
+            },
+        };
+        let live = Monitor::parse_monitors(&stdout);
+        let mut lines = Monitor::reconcile_modes(&mut app.monitors, &live);
+        if lines.is_empty() {
+            lines.push("Every selected mode is running".to_string());
+        }
+        app.popup = Some(Popup::new("Reconcile", lines));
+    }
+
+    fn validate(app:&mut App) {
+        let mut issues = Monitor::validate(&app.monitors);
+        if issues.is_empty() {
+            issues.push("No issue found".to_string());
+        }
+        app.popup = Some(Popup::new("Validate", issues).with_scroll());
+    }
+
+    // Copies the selected monitor's Hyprland line with wl-copy, shown in
+    // the popup either way so it can be copied by hand without wl-copy.
+    fn copy_config_line(app:&mut App) {
+        let line = app.monitors[app.selected_monitor].to_hyprland_config(app.config.minimal_hyprland_config);
+        app.popup = Some(match app.runner.run("wl-copy", std::slice::from_ref(&line)) {
+            Ok(_) => Popup::new("Copied", vec![line]),
+            Err(e) => {
+                logger::warn(&format!("Failed to run wl-copy: {}", e));
+                Popup::new("Copy failed", vec![line, format!("wl-copy: {}", e)])
+            },
+        });
+    }
+
     fn change_mode(app:&mut App,mode: TUIMode) {
+        // a disabled monitor has no current mode to edit
+        let monitor = &app.monitors[app.selected_monitor];
+        if matches!(mode, TUIMode::Resolution | TUIMode::Scale) && !monitor.enabled {
+            app.popup = Some(Popup::new("Disabled", vec![
+                format!("{} is disabled", monitor.name),
+                "Enable it with <e> first".to_string(),
+            ]));
+            return;
+        }
         app.mode = mode;
     }
 
     fn next_monitor(app:&mut App) {
-        app.selected_monitor = if app.selected_monitor >= app.monitors.len() - 1 {
-            0
-        } else {
-            app.selected_monitor + 1
-        }
+        app.selected_monitor = utils::next_index(app.selected_monitor, app.monitors.len(), app.config.wrap_navigation);
     }
 
     fn previous_monitor(app:&mut App) {
-        app.selected_monitor = if app.selected_monitor == 0 {
-            app.monitors.len() - 1
-        } else {
-            app.selected_monitor - 1
-        }
+        app.selected_monitor = utils::previous_index(app.selected_monitor, app.monitors.len(), app.config.wrap_navigation);
     }
     
     fn disable_monitor(app:&mut App) {
-        let monitor = &mut app.monitors[app.selected_monitor];
-        monitor.enabled = false;
-        monitor.saved_position = monitor.position.clone();
-        monitor.saved_scale = monitor.scale;
+        if app.config.close_gaps && app.monitors[app.selected_monitor].enabled {
+            Monitor::close_gap(&mut app.monitors, app.selected_monitor);
+        }
+        app.monitors[app.selected_monitor].disable();
     }
 
     fn enable_monitor(app:&mut App) {
-        let monitor = &mut app.monitors[app.selected_monitor];
-        monitor.enabled = true;
-        if let Some(saved_pos) = &monitor.saved_position {
-            monitor.position = Some(Position {
-                x: saved_pos.x,
-                y: saved_pos.y,
-            });
+        let was_enabled = app.monitors[app.selected_monitor].enabled;
+        app.monitors[app.selected_monitor].enable();
+        if app.config.close_gaps && !was_enabled {
+            Monitor::open_gap(&mut app.monitors, app.selected_monitor);
+        }
+    }
+
+    fn disable_all_but_selected(app:&mut App) {
+        app.monitors[app.selected_monitor].enable();
+        for (i, monitor) in app.monitors.iter_mut().enumerate() {
+            if i != app.selected_monitor {
+                monitor.disable();
+            }
+        }
+    }
+
+    // Selected monitor alone at its preferred mode, scale 1 and 0x0 for
+    // screen sharing. Pressed again, puts every monitor back as it was.
+    fn toggle_presentation(app:&mut App) {
+        if let Some(monitors) = app.presentation_snapshot.take() {
+            app.monitors = monitors;
+            return;
+        }
+        app.presentation_snapshot = Some(app.monitors.clone());
+        for (i, monitor) in app.monitors.iter_mut().enumerate() {
+            if i != app.selected_monitor {
+                monitor.disable();
+                continue;
+            }
+            monitor.enable();
+            if let Some(index) = monitor.modes.iter().position(|m| m.preferred) {
+                monitor.set_current_resolution(index);
+            }
+            monitor.scale = Some(1.0);
+            monitor.position = Some(Position { x: 0, y: 0 });
+        }
+    }
+
+    fn enable_all(app:&mut App) {
+        for monitor in app.monitors.iter_mut() {
+            monitor.enable();
         }
-        // If no saved_position (from disable), keep the current position
-        // which might have been loaded from the persistent state file
-        monitor.scale = monitor.saved_scale.or_else(|| monitor.scale).or(Some(1.0));
     }
 
     fn monitors_to_rows(&self) -> Vec<Row<'static>> {
         self.monitors
             .iter()
-            .map(|monitor| {
-                let name = monitor.name.clone();
+            .enumerate()
+            .map(|(index, monitor)| {
+                let mut tags = String::new();
+                if monitor.focused {
+                    tags.push_str(" [focused]");
+                }
+                if monitor.locked {
+                    tags.push_str(" [locked]");
+                }
+                let enabled = monitor.enabled.to_string();
+                let number = Cell::default().content(
+                    Line::from(
+                        // numbered like on the map
+                        format!("{} {}", index + 1, if enabled == "true" { "" } else { "" })
+                    )
+                    .centered()
+                    .style(
+                        Style::default().fg(
+                            if enabled == "true" {Color::Green} else {Color::Red}
+                        )
+                    ),
+                );
+                if self.compact {
+                    return Row::new(vec![number, Cell::from(monitor.list_row_string(true) + &tags)]);
+                }
+                let name = monitor.list_row_string(false) + &tags;
                 let description = monitor.description.clone().unwrap_or_else(|| "No description".to_string());
                 let scale = monitor.scale.unwrap_or(1.0).to_string();
-                let enabled = monitor.enabled.to_string();
                 
                 let position = match monitor.position.as_ref() {
-                    Some(pos) => format!("({},{})", pos.x, pos.y),
+                    Some(pos) => {
+                        let scale = monitor.scale.unwrap_or(1.0);
+                        format!("({},{})", self.coordinates.convert(pos.x, scale), self.coordinates.convert(pos.y, scale))
+                    },
                     None => "N/A".to_string(),
                 };
 
-                let rotation = monitor.transform.clone().unwrap_or("normal".to_string());
+                let rotation = Rotation::from_transform(&monitor.transform);
+                let rotation_label = match rotation {
+                    Rotation::Normal => "normal".to_string(),
+                    _ => format!("⟳{}", rotation.to_transform()),
+                };
 
                 let mut mode = monitor.get_current_resolution();
                 if mode.is_none() {
                     mode = monitor.get_prefered_resolution();
                 }
+                // as laid out, like get_geometry
                 let resolution = match mode{
+                    Some(res) if rotation.is_portrait() => format!("{}x{}", res.height, res.width),
                     Some(res) => format!("{}x{}", res.width, res.height),
                     None => "N/A".to_string(),
                 };
                 Row::new(vec![
-                    Cell::default().content(
-                        Line::from(
-                            if enabled == "true" {
-                                "".green().to_string()
-                            } else {
-                                "".red().to_string()
-                            }
-                        )
-                        .centered()
-                        .style(
-                            Style::default().fg(
-                                if enabled == "true" {Color::Green} else {Color::Red}
-                            )
-                        ),
-                    ),
+                    number,
                     Cell::from(name),
                     Cell::from(description),
                     Cell::from(resolution), 
                     Cell::from(position),
                     Cell::from(scale),
-                    Cell::from(rotation),
+                    Cell::from(rotation_label),
                 ])
             }
             )
@@ -149,98 +396,37 @@ impl<'a> MonitorList<'a> {
     
     pub fn render(&mut self, area: Rect, buf: &mut Buffer) {
         let title = Line::from(if self.monitors.len()>1 {" Displays "}else{" Display "}.white().bold());
-        let mut instructions_items = vec![];
-
-        match self.mode {
-            TUIMode::View => {
-                let selected_monitor = &self.monitors[self.selected_row.unwrap_or(0)];
-                instructions_items.push(" Up ".white());
-                instructions_items.push("<k> ".blue().bold());
-                instructions_items.push(" Down ".white());
-                instructions_items.push("<j> ".blue().bold());
-                instructions_items.push(" Move ".white());
-                instructions_items.push("<m> ".blue().bold());
-                instructions_items.push(" Resolution ".white());
-                instructions_items.push("<r> ".blue().bold());
-                instructions_items.push(" Scale ".white());
-                instructions_items.push("<s> ".blue().bold());
-                instructions_items.push(" Rotate ".white());
-                instructions_items.push("<o> ".blue().bold());
-                if selected_monitor.enabled {
-                    instructions_items.push(" Disable ".white());
-                    instructions_items.push("<d> ".blue().bold());
-                } else {
-                    instructions_items.push(" Enable ".white());
-                    instructions_items.push("<e> ".blue().bold());
-                }
-            },
-
-            TUIMode::Resolution=> {
-                instructions_items.push(" Up ".white());
-                instructions_items.push("<k> ".blue().bold());
-                instructions_items.push(" Down ".white());
-                instructions_items.push("<j> ".blue().bold());
-                instructions_items.push(" Select ".white());
-                instructions_items.push("<Space> ".blue().bold());
-                instructions_items.push(" Quit Resolution Mode ".white());
-                instructions_items.push("<Esc> ".blue().bold());
-            },
-
-            TUIMode::Move => {
-                instructions_items.push(" Fast ".white());
-                instructions_items.push("<MAJ>+<*> ".blue().bold());
-                instructions_items.push(" Up ".white());
-                instructions_items.push("<k> ".blue().bold());
-                instructions_items.push(" Down ".white());
-                instructions_items.push("<j> ".blue().bold());
-                instructions_items.push(" Left ".white());
-                instructions_items.push("<h> ".blue().bold());
-                instructions_items.push(" Right ".white());
-                instructions_items.push("<l> ".blue().bold());
-                instructions_items.push(" Quit Move Mode ".white());
-                instructions_items.push("<Esc> ".blue().bold());
-            },
-            TUIMode::Scale => {
-                instructions_items.push(" Up ".white());
-                instructions_items.push("<k> ".blue().bold());
-                instructions_items.push(" Down ".white());
-                instructions_items.push("<j> ".blue().bold());
-                instructions_items.push(" Select ".white());
-                instructions_items.push("<Space> ".blue().bold());
-                instructions_items.push(" Quit Scale Mode ".white());
-                instructions_items.push("<Esc> ".blue().bold());
-            },
-        }
+        let selected_enabled = self.monitors.get(self.selected_row.unwrap_or(0)).is_some_and(|m| m.enabled);
+        let hidden = if selected_enabled { "Enable" } else { "Disable" };
+        let instructions = keybindings::footer(&keybindings::defaults(), self.mode, &[hidden]);
 
-        instructions_items.push(" Save ".white());
-        instructions_items.push("<w> ".blue().bold());
-        instructions_items.push(" Quit ".white());
-        instructions_items.push("<q> ".blue().bold());
-
-        let instructions = Line::from(instructions_items);
-
-        let block = Block::bordered()
+        let mut block = Block::bordered()
             .title(title.centered())
             .title_bottom(instructions.centered())
             .border_set(border::THICK)
             .border_style(Style::default().fg(
                 if self.mode == TUIMode::View {Color::Yellow} else {Color::White}));
+        if let Some(status) = &self.status {
+            block = block.title_bottom(Line::from(format!(" {} ", status).white()).right_aligned());
+        }
 
-        let widths = [
-            
-            Constraint::Percentage(5),
-            Constraint::Percentage(15),
-            Constraint::Percentage(35),
-            Constraint::Percentage(15),
-            Constraint::Percentage(10),
-            Constraint::Percentage(10),
-            Constraint::Percentage(10),
-        ];   
-
-        let table = Table::new(self.monitors_to_rows(),widths) 
-            .column_spacing(1)
-            .header(
-                Row::new(vec![
+        let widths = if self.compact {
+            vec![Constraint::Percentage(5), Constraint::Percentage(95)]
+        } else {
+            vec![
+                Constraint::Percentage(5),
+                Constraint::Percentage(15),
+                Constraint::Percentage(35),
+                Constraint::Percentage(15),
+                Constraint::Percentage(10),
+                Constraint::Percentage(10),
+                Constraint::Percentage(10),
+            ]
+        };
+        let header = if self.compact {
+            Row::new(vec![Cell::from("  "), Cell::from("display")])
+        } else {
+            Row::new(vec![
                     Cell::default().content(
                         Line::from("  ")
                         .centered()
@@ -248,10 +434,19 @@ impl<'a> MonitorList<'a> {
                     Cell::from("name"),
                     Cell::from("description"),
                     Cell::from("resolution"),
-                    Cell::from("position"),
+                    Cell::from(match self.coordinates {
+                        Coordinates::Logical => "position",
+                        Coordinates::Native => "native pos",
+                    }),
                     Cell::from("scale"),
                     Cell::from("rotation")
                 ])
+        };
+
+        let table = Table::new(self.monitors_to_rows(),widths) 
+            .column_spacing(1)
+            .header(
+                header
                     .bottom_margin(1)
                     .bold()
                     .green()
@@ -275,7 +470,9 @@ impl<'a> MonitorList<'a> {
 mod tests {
     use super::*;
     use ratatui::style::Style;
-    use crate::test_utils::tests::test_monitors;
+    use std::rc::Rc;
+    use crate::runner::Runner;
+    use crate::test_utils::tests::{row_text, test_monitors, FakeRunner};
 
     #[test]
     fn render_list() {
@@ -284,6 +481,9 @@ mod tests {
             selected_row: Some(0),
             mode: TUIMode::View,
             monitors: &test_monitors(),
+            coordinates: Coordinates::Logical,
+            status: None,
+            compact: false,
         }; 
         let mut buf = Buffer::empty(Rect::new(0, 0, 110, 7));
         
@@ -293,8 +493,8 @@ mod tests {
             "┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━ Displays ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓",
             "┃     name              description                     resolution        position   scale       rotation   ┃",
             "┃                                                                                                            ┃",
-            "┃     Monitor 1         Description 1                   1920x1080         (0,0)      1           normal     ┃",
-            "┃     Monitor 2         Description 2                   1280x720          (1920,0)   1.25        normal     ┃",
+            "┃ 1   Monitor 1         Description 1                   1920x1080         (0,0)      1           normal     ┃",
+            "┃ 2   Monitor 2         Description 2                   1280x720          (1920,0)   1.25        normal     ┃",
             "┃                                                                                                            ┃",
             "┗━━━━ Up <k>  Down <j>  Move <m>  Resolution <r>  Scale <s>  Rotate <o>  Disable <d>  Save <w>  Quit <q> ━━━━┛",
         ]);
@@ -365,4 +565,59 @@ mod tests {
 
         assert_eq!(buf, expected);
     }
+
+    #[test]
+    fn render_list_marks_focused_monitor() {
+        let stdout = r#"[
+            {"name": "DP-1", "enabled": true, "focused": true, "modes": [], "position": {"x": 0, "y": 0}, "scale": 1.0},
+            {"name": "DP-2", "enabled": true, "modes": [], "position": {"x": 1920, "y": 0}, "scale": 1.0}
+        ]"#;
+        let runner = Runner(Rc::new(FakeRunner::with_output("wlr-randr", stdout)));
+        let monitors = Monitor::get_monitors(&runner);
+        let mut list = MonitorList::new(&monitors, TUIMode::View, Some(0));
+        let mut buf = Buffer::empty(Rect::new(0, 0, 110, 7));
+
+        list.render(buf.area, &mut buf);
+
+        let row = |y: u16| row_text(&buf, y);
+        assert!(row(3).contains("DP-1 [focused]"), "{}", row(3));
+        assert!(!row(4).contains("[focused]"), "{}", row(4));
+    }
+
+    #[test]
+    fn render_list_rotated_monitor() {
+        let mut monitors = test_monitors();
+        monitors[0].transform = Some("90".to_string());
+        let mut list = MonitorList::new(&monitors, TUIMode::View, Some(0));
+        let mut buf = Buffer::empty(Rect::new(0, 0, 110, 7));
+
+        list.render(buf.area, &mut buf);
+
+        let row = |y: u16| row_text(&buf, y);
+        assert!(row(3).contains("1080x1920"), "{}", row(3));
+        assert!(row(3).contains("⟳90"), "{}", row(3));
+        assert!(row(4).contains("1280x720"), "{}", row(4));
+        assert!(row(4).contains("normal"), "{}", row(4));
+    }
+
+    #[test]
+    fn render_list_native_coordinates() {
+        let monitors = test_monitors();
+        let render = |coordinates: Coordinates| {
+            let mut list = MonitorList::new(&monitors, TUIMode::View, Some(0)).with_coordinates(coordinates);
+            let mut buf = Buffer::empty(Rect::new(0, 0, 110, 7));
+            list.render(buf.area, &mut buf);
+            (1..5).map(|y| row_text(&buf, y)).collect::<Vec<_>>()
+        };
+
+        // Monitor 2 is at 1920x0 with a 1.25 scale
+        let logical = render(Coordinates::Logical);
+        assert!(logical[0].contains("position"), "{}", logical[0]);
+        assert!(logical[3].contains("(1920,0)"), "{}", logical[3]);
+
+        let native = render(Coordinates::Native);
+        assert!(native[0].contains("native pos"), "{}", native[0]);
+        assert!(native[3].contains("(2400,0)"), "{}", native[3]);
+        assert!(native[2].contains("(0,0)"), "{}", native[2]);
+    }
 }
@@ -0,0 +1,98 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Writing to stderr corrupts the TUI, so messages go to a file instead:
+// $DISPLAY_TUI_LOG if set, ~/.config/display-tui/log.txt otherwise.
+// Logging never fails the app, an unwritable path just drops the message.
+
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+
+pub fn init(verbose: bool) {
+    VERBOSE.store(verbose, Ordering::Relaxed);
+}
+
+pub fn error(message: &str) {
+    log("ERROR", message);
+}
+
+pub fn warn(message: &str) {
+    log("WARN", message);
+}
+
+// Only written with --verbose.
+pub fn debug(message: &str) {
+    if VERBOSE.load(Ordering::Relaxed) {
+        log("DEBUG", message);
+    }
+}
+
+fn log(level: &str, message: &str) {
+    if let Some(path) = log_path() {
+        let _ = write_line(&path, level, message);
+    }
+}
+
+#[cfg(not(test))]
+fn log_path() -> Option<PathBuf> {
+    match std::env::var("DISPLAY_TUI_LOG") {
+        Ok(path) if !path.is_empty() => Some(PathBuf::from(shellexpand::tilde(&path).to_string())),
+        _ => dirs::home_dir().map(|p| p.join(".config/display-tui/log.txt")),
+    }
+}
+
+// Tests hitting warn and error paths must not fill the user's log.
+#[cfg(test)]
+fn log_path() -> Option<PathBuf> {
+    Some(std::env::temp_dir().join("display-tui-test").join("log.txt"))
+}
+
+fn write_line(path: &Path, level: &str, message: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{} {} {}", timestamp, level, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_line_appends() {
+        let dir = std::env::temp_dir().join("display-tui-logger-test");
+        let path = dir.join("log.txt");
+        let _ = fs::remove_dir_all(&dir);
+
+        write_line(&path, "WARN", "first").expect("Failed to write log");
+        write_line(&path, "ERROR", "second").expect("Failed to write log");
+
+        let content = fs::read_to_string(&path).expect("Failed to read log");
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with(" WARN first"), "{}", lines[0]);
+        assert!(lines[1].ends_with(" ERROR second"), "{}", lines[1]);
+    }
+
+    #[test]
+    fn log_path_in_tests() {
+        let path = log_path().expect("No log path");
+        assert!(path.starts_with(std::env::temp_dir()), "{}", path.display());
+    }
+
+    #[test]
+    fn write_line_unwritable_path() {
+        // the parent is a file, so the directory can't be created
+        let file = std::env::temp_dir().join("display-tui-logger-not-a-dir");
+        fs::write(&file, "").expect("Failed to create file");
+
+        assert!(write_line(&file.join("log.txt"), "WARN", "lost").is_err());
+    }
+}
@@ -1,9 +1,10 @@
 use std::io;
-use crossterm::event::{self,Event,KeyCode,KeyEvent,KeyEventKind};
+use std::time::{Duration, Instant, SystemTime};
+use crossterm::event::{self,Event,KeyCode,KeyEvent,KeyEventKind,KeyModifiers};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
-    widgets::Widget,
+    widgets::{Block,Paragraph,Widget},
     DefaultTerminal,Frame,
 };
 use ratatui::prelude::*;
@@ -14,25 +15,71 @@ mod rotation;
 mod resolutions;
 mod utils;
 mod scale;
+mod settings;
+mod arrange;
 mod configuration;
+mod cli;
+mod popup;
+mod runner;
+mod logger;
+mod keybindings;
+mod config_editor;
 mod test_utils;
 
 use list::MonitorList;
-use map::Map;
-use monitor::Monitor;
+use map::{Map, Viewport};
+use monitor::{Monitor, MonitorDetection, Position};
 
 use resolutions::Resolutions; 
 use scale::Scale;
-use utils::TUIMode;
-use configuration::Configuration;
+use settings::Settings;
+use arrange::Arrange;
+use config_editor::ConfigEditor;
+use utils::{TUIMode,LayoutPreset,Coordinates,ColorSupport,ConfigFormat};
+use configuration::{Configuration, MonitorState};
+use cli::CliOptions;
+use popup::Popup;
+use runner::Runner;
 
 fn main() -> io::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let options = CliOptions::parse(&args)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    logger::init(options.verbose);
+    let runner = match &options.monitors_from {
+        Some(path) => Runner::with_monitors_from(path)?,
+        None => Runner::default(),
+    };
+    if options.export {
+        let mut app = App::load(runner, Configuration::get());
+        app.handle_keys(&options.keys);
+        let path = cli::export(&options, &app.monitors, &app.config)?;
+        println!("{}", path);
+        return Ok(());
+    }
+
     let mut terminal = ratatui::init();
-    let app_result = App::default().run(&mut terminal);
+    let mut app = match &options.monitors_from {
+        Some(_) => App::load(runner, Configuration::get()),
+        None => App::detecting(runner, Configuration::get(), MonitorDetection::wlr_randr()),
+    };
+    let app_result = app.run(&mut terminal);
     ratatui::restore();
     app_result
 }
 
+// Longest wait for input before redrawing
+const TICK: Duration = Duration::from_secs(1);
+// Shortest time between two hyprctl calls while moving with live_move
+const LIVE_APPLY_INTERVAL: Duration = Duration::from_millis(100);
+// Frame length of the pulse_selection animation
+const PULSE_INTERVAL: Duration = Duration::from_millis(400);
+// Frame length of the spinner shown while wlr-randr runs
+const SPINNER_INTERVAL: Duration = Duration::from_millis(100);
+// Time Hyprland gets to reload the config before `V` detects the monitors
+const VERIFY_DELAY: Duration = Duration::from_millis(500);
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
 #[derive(Debug, Default)]
 struct App {
     exit:bool,
@@ -41,31 +88,141 @@ struct App {
     selected_monitor: usize,
     selected_resolution : usize,
     selected_scale: usize,
+    selected_setting: usize,
+    selected_arrangement: usize,
+    selected_config_field: usize,
+    editing_text: Option<String>,
     mode: TUIMode,
+    layout: LayoutPreset,
+    show_grid: bool,
+    // native size outlines on the map, toggled with F
+    show_footprint: bool,
+    // map pan, reset with f
+    viewport: Viewport,
+    coordinates: Coordinates,
+    // mixed scale cursor note hidden with X
+    cursor_note_dismissed: bool,
+    // scale hovered in Scale mode, see Scale::update_preview
+    scale_preview: Option<f32>,
+    // monitors before the presentation preset, see MonitorList::toggle_presentation
+    presentation_snapshot: Option<Vec<Monitor>>,
+    // monitors as last written and as last applied to the compositor,
+    // compared with the working state for the footer indicators
+    saved_monitors: Option<Vec<Monitor>>,
+    applied_monitors: Option<Vec<Monitor>>,
+    // for the session time in the footer, unset in tests
+    started: Option<Instant>,
+    // with live_move: monitors when Move mode was entered, restored on Esc,
+    // and the time of the last live apply
+    move_snapshot: Option<Vec<Monitor>>,
+    live_applied: Option<Instant>,
+    // advanced on each tick without input, for pulse_selection and the spinner
+    frame: u64,
+    // wlr-randr still running at startup, monitors is empty until it answers
+    detection: Option<MonitorDetection>,
+    popup: Option<Popup>,
+    // question asked by the popup, answered by the key closing it
+    prompt: Option<Prompt>,
+    // monitors_config_path's modification time when loaded or last written,
+    // unset when it didn't exist
+    config_modified: Option<SystemTime>,
+    // basic terminals get the map's fallback selection styling
+    colors: ColorSupport,
+    runner: Runner,
+    // wait between hyprctl reload and wlr-randr in `V`, zero in tests
+    verify_delay: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Prompt {
+    // y quits without writing
+    Quit,
+    // y overwrites the file changed on disk and resumes the action, r reloads it
+    Overwrite(WriteAction),
+}
+
+// The keys that write the config, resumed after the Overwrite prompt.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum WriteAction {
+    Write,
+    Apply,
+    ApplyAndQuit,
+    Verify,
 }
 
 impl App{
-    pub fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
-        self.monitors = Monitor::get_monitors();
-        
-        // Load saved monitor positions/scales
-        if let Some(saved_states) = Configuration::load_monitor_state() {
-            for monitor in &mut self.monitors {
-                if let Some(saved_state) = saved_states.iter().find(|s| s.name == monitor.name) {
-                    if let Some(pos) = &saved_state.position {
-                        monitor.position = Some(pos.clone());
-                    }
-                    if let Some(scale) = saved_state.scale {
-                        monitor.scale = Some(scale);
-                    }
-                }
-            }
+    // The app state without a terminal, driven by handle_key_event.
+    fn load(runner: Runner, config: Configuration) -> Self {
+        let monitors = App::load_monitors(&runner, &config);
+        App {
+            saved_monitors: Some(monitors.clone()),
+            applied_monitors: Some(monitors.clone()),
+            monitors,
+            config_modified: utils::modified_time(&config.monitors_config_path),
+            started: Some(Instant::now()),
+            colors: ColorSupport::detect(),
+            verify_delay: VERIFY_DELAY,
+            runner,
+            config,
+            ..Default::default()
         }
-        
-        self.selected_resolution= 0;
-        self.selected_monitor= 0;
-        self.config = Configuration::get();
+    }
+
+    // The app drawing a spinner until `detection` gives the monitors.
+    fn detecting(runner: Runner, config: Configuration, detection: MonitorDetection) -> Self {
+        App {
+            detection: Some(detection),
+            started: Some(Instant::now()),
+            colors: ColorSupport::detect(),
+            verify_delay: VERIFY_DELAY,
+            runner,
+            config,
+            ..Default::default()
+        }
+    }
+
+    fn finish_detection(&mut self) {
+        let Some(monitors) = self.detection.as_ref().and_then(|d| d.try_finish()) else { return };
+        let monitors = App::prepare_monitors(monitors, &self.config);
+        self.saved_monitors = Some(monitors.clone());
+        self.applied_monitors = Some(monitors.clone());
+        self.monitors = monitors;
+        self.config_modified = utils::modified_time(&self.config.monitors_config_path);
+        self.detection = None;
+        if self.monitors.is_empty() {
+            self.popup = Some(Popup::new("No displays", vec![
+                "wlr-randr reported no displays, or failed to run".to_string(),
+                "See the log for details".to_string(),
+            ]));
+        }
+    }
+
+    fn unsaved(&self) -> bool {
+        self.saved_monitors.as_ref().is_some_and(|saved| *saved != self.monitors)
+    }
+
+    fn unapplied(&self) -> bool {
+        self.applied_monitors.as_ref().is_some_and(|applied| *applied != self.monitors)
+    }
+
+    fn live_move_pending(&self) -> bool {
+        self.config.live_move && self.mode == TUIMode::Move && self.unapplied()
+    }
+
+    // Applies the moved layout at most once per LIVE_APPLY_INTERVAL, the
+    // last move is applied by the first call after the interval.
+    fn live_apply(&mut self, now: Instant) {
+        if !self.live_move_pending() {
+            return;
+        }
+        if self.live_applied.is_some_and(|last| now.duration_since(last) < LIVE_APPLY_INTERVAL) {
+            return;
+        }
+        self.apply();
+        self.live_applied = Some(now);
+    }
 
+    pub fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
         while !self.exit {
             terminal.draw(|frame| self.draw(frame))?;
             self.handle_events()?;
@@ -73,92 +230,549 @@ impl App{
         Ok(())
     }
 
+    fn load_monitors(runner: &Runner, config: &Configuration) -> Vec<Monitor> {
+        App::prepare_monitors(Monitor::get_monitors(runner), config)
+    }
+
+    // Aliases, default scale, then the Hyprland config or the saved state.
+    fn prepare_monitors(mut monitors: Vec<Monitor>, config: &Configuration) -> Vec<Monitor> {
+        for monitor in &mut monitors {
+            monitor.alias = config.aliases.get(&monitor.name).cloned();
+            // some outputs report no position for disabled or new monitors
+            if monitor.position.is_none() {
+                monitor.position = Some(Position::default());
+            }
+            // overridden below by the Hyprland config or the saved state when they have one
+            if let Some(scale) = config.default_scale
+                && monitor.scale.is_none_or(|s| s == 1.0)
+            {
+                monitor.scale = Some(scale);
+            }
+        }
+        let saved_states = config.load_monitor_state();
+
+        // First launch: seed the layout from an existing Hyprland config
+        if saved_states.is_none()
+            && let Some(lines) = Monitor::load_hyprland_config(&config.monitors_config_path)
+        {
+            for monitor in &mut monitors {
+                if let Some(line) = lines.iter().find(|l| l.name == monitor.name) {
+                    monitor.apply_hyprland_config(line);
+                }
+            }
+        }
+        
+        if let Some(saved_states) = saved_states {
+            App::apply_saved_state(&mut monitors, &saved_states);
+        }
+        // after the saved state, which holds the refresh cap
+        for monitor in &mut monitors {
+            monitor.ensure_current_resolution();
+        }
+        monitors
+    }
+
+    // Load saved monitor positions/scales
+    fn apply_saved_state(monitors: &mut [Monitor], saved_states: &[MonitorState]) {
+        for monitor in monitors {
+            if let Some(saved_state) = saved_states.iter().find(|s| s.name == monitor.name) {
+                if let Some(pos) = &saved_state.position {
+                    monitor.position = Some(pos.clone());
+                }
+                if let Some(scale) = saved_state.scale {
+                    monitor.scale = Some(scale);
+                }
+                monitor.locked = saved_state.locked;
+                monitor.default_workspace = saved_state.default_workspace.clone();
+                monitor.refresh_cap = saved_state.refresh_cap;
+                monitor.mode_keyword = saved_state.mode_keyword;
+            }
+        }
+    }
+
     fn draw(&self, frame: &mut Frame){
         frame.render_widget(self,frame.area());
     }
 
+    // Scripted input, stops at the first key that quits.
+    fn handle_keys(&mut self, keys: &[KeyEvent]) {
+        for key_event in keys {
+            if self.exit {
+                break;
+            }
+            self.handle_key_event(*key_event);
+        }
+    }
+
     fn handle_events(&mut self) -> io::Result<()> {
-        match event::read()? {
-            Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
-                self.handle_key_event(key_event)
+        self.handle_pending_events(event::poll, event::read)
+    }
+
+    // Waits up to TICK for an event, so the footer clock is redrawn even
+    // without input, then also handles the ones already queued so a burst
+    // of key repeats leads to a single redraw. A pending live move shortens
+    // the wait so it is applied once the throttle allows, and so does the
+    // startup detection to animate its spinner.
+    fn handle_pending_events(
+        &mut self,
+        mut poll: impl FnMut(Duration) -> io::Result<bool>,
+        mut read: impl FnMut() -> io::Result<Event>,
+    ) -> io::Result<()> {
+        let timeout = if self.detection.is_some() {
+            SPINNER_INTERVAL
+        } else if self.live_move_pending() {
+            LIVE_APPLY_INTERVAL
+        } else if self.config.pulse_selection {
+            PULSE_INTERVAL
+        } else {
+            TICK
+        };
+        if poll(timeout)? {
+            let mut first = Some(read()?);
+            self.handle_event_batch(|| match first.take() {
+                Some(event) => Ok(Some(event)),
+                None if poll(Duration::ZERO)? => read().map(Some),
+                None => Ok(None),
+            })?;
+        } else {
+            self.frame += 1;
+        }
+        self.finish_detection();
+        self.live_apply(Instant::now());
+        Ok(())
+    }
+
+    // Handles events from `next_event` until it returns None or the app exits.
+    fn handle_event_batch(&mut self, mut next_event: impl FnMut() -> io::Result<Option<Event>>) -> io::Result<()> {
+        while !self.exit && let Some(event) = next_event()? {
+            match event {
+                Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
+                    self.handle_key_event(key_event)
+                }
+                // nothing to update, the next draw uses the new size
+                Event::Resize(..) => {}
+                _ => {}
             }
-            _ => {}
         }
         Ok(())
     }
 
     fn handle_key_event(&mut self, key_event: KeyEvent) {
+        if let Some(popup) = self.popup.as_mut().filter(|p| p.scrollable) {
+            match key_event.code {
+                KeyCode::Char('j') | KeyCode::Down => { popup.scroll_by(1); return; },
+                KeyCode::Char('k') | KeyCode::Up => { popup.scroll_by(-1); return; },
+                _ => {}
+            }
+        }
+        if self.popup.is_some() {
+            self.popup = None;
+            match (self.prompt.take(), key_event.code) {
+                (Some(Prompt::Quit), KeyCode::Char('y')) => self.exit(),
+                (Some(Prompt::Overwrite(action)), KeyCode::Char('y')) => {
+                    self.config_modified = utils::modified_time(&self.config.monitors_config_path);
+                    self.start_write(action);
+                },
+                (Some(Prompt::Overwrite(_)), KeyCode::Char('r')) => self.reload_monitors_config(),
+                _ => {},
+            }
+            return;
+        }
+        // no monitors (yet), nothing to edit and quitting must not save the
+        // empty state
+        if self.detection.is_some() || self.monitors.is_empty() {
+            if matches!(key_event.code, KeyCode::Char('q') | KeyCode::Esc) {
+                self.exit = true;
+            }
+            return;
+        }
+        // typing a label must not trigger the global keys
+        if self.editing_text.is_some() {
+            match self.mode {
+                TUIMode::Config => ConfigEditor::handle_events(self,key_event),
+                _ => Settings::handle_events(self,key_event),
+            }
+            return;
+        }
         match key_event.code {
-            KeyCode::Char('q') => self.exit(),
-            KeyCode::Char('w') => self.write(), 
+            KeyCode::Char('s') if key_event.modifiers.contains(KeyModifiers::CONTROL) => self.start_write(WriteAction::Apply),
+            KeyCode::Char('Z') => self.start_write(WriteAction::ApplyAndQuit),
+            KeyCode::Char('V') => self.start_write(WriteAction::Verify),
+            KeyCode::Char('q') => self.quit(),
+            KeyCode::Char('w') => self.start_write(WriteAction::Write),
+            KeyCode::Char('W') => self.write_selected(),
+            KeyCode::Char('v') => self.layout = self.layout.cycle(),
+            KeyCode::Char('G') => self.show_grid = !self.show_grid,
+            KeyCode::Char('F') => self.show_footprint = !self.show_footprint,
+            KeyCode::Char('f') => self.viewport = Viewport::default(),
+            KeyCode::Char('n') => self.coordinates = self.coordinates.toggle(),
+            KeyCode::Char('?') => self.show_help(),
+            KeyCode::Char('X') => self.cursor_note_dismissed = true,
+            code if !self.config.movement_keys.allows(code) => {},
             _ => {
                 match self.mode {
                     TUIMode::View => MonitorList::handle_events(self,key_event),
                     TUIMode::Move => Map::handle_events(self,key_event),
                     TUIMode::Resolution=> Resolutions::handle_events(self,key_event),
                     TUIMode::Scale => Scale::handle_events(self,key_event), 
+                    TUIMode::Settings => Settings::handle_events(self,key_event),
+                    TUIMode::Arrange => Arrange::handle_events(self,key_event),
+                    TUIMode::Config => ConfigEditor::handle_events(self,key_event),
                 }
             }
         }
     }
     
+    fn show_help(&mut self) {
+        let lines = keybindings::help_lines(&keybindings::defaults())
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+        self.popup = Some(Popup::new("Keys", lines));
+    }
+
+    // `q` only saves the monitor state, so unwritten changes ask first.
+    fn quit(&mut self) {
+        if !self.config.confirm_quit || !self.unsaved() {
+            self.exit();
+            return;
+        }
+        self.popup = Some(
+            Popup::new("Unsaved changes", vec!["Quit without writing config? (y/n)".to_string()])
+                .with_hint(" Quit <y>  Stay <n> ")
+        );
+        self.prompt = Some(Prompt::Quit);
+    }
+
     fn exit(&mut self) {
         // Save monitor state before exiting
-        if let Err(e) = Configuration::save_monitor_state(&self.monitors) {
-            eprintln!("Warning: Failed to save monitor state on exit: {}", e);
+        if let Err(e) = self.config.save_monitor_state(&self.monitors) {
+            logger::warn(&format!("Failed to save monitor state on exit: {}", e));
         }
         self.exit = true;
     }
     
-    fn write(&mut self) {
-        Monitor::save_hyprland_config(
-            &self.config.monitors_config_path,
-            &self.monitors
-        ).expect("Failed to save Hyprland config");
-        
-        match Configuration::save_monitor_state(&self.monitors) {
-            Ok(_) => eprintln!("✓ Monitor state saved successfully"),
-            Err(e) => eprintln!("✗ Failed to save monitor state: {}", e),
+    // Runs `action`, remembered by the Overwrite prompt if write asks it.
+    fn start_write(&mut self, action: WriteAction) {
+        match action {
+            WriteAction::Write => { self.write(); },
+            WriteAction::Apply => { self.write_and_apply(); },
+            WriteAction::ApplyAndQuit => self.write_apply_and_quit(),
+            WriteAction::Verify => self.write_and_verify(),
+        }
+        if let Some(Prompt::Overwrite(resume)) = &mut self.prompt {
+            *resume = action;
         }
+    }
+
+    // Writes every output target. Returns false, with the error shown in a
+    // popup, if one could not be written. The others are still written.
+    fn write(&mut self) -> bool {
+        if self.changed_on_disk() {
+            self.popup = Some(
+                Popup::new("Changed on disk", vec![
+                    format!("{} was modified outside display-tui", self.config.monitors_config_path),
+                    "Overwrite it, or reload its monitors first?".to_string(),
+                ]).with_hint(" Overwrite <y>  Reload <r>  Cancel <any key> ")
+            );
+            self.prompt = Some(Prompt::Overwrite(WriteAction::Write));
+            return false;
+        }
+        let targets = self.config.write_targets();
+        let mut report = vec![];
+        let mut failed = false;
+        for target in &targets {
+            let split = self.config.split_monitor_files
+                && matches!(target.format, ConfigFormat::Hyprland | ConfigFormat::HyprlandV2);
+            let saved = if split {
+                Monitor::save_split_config(&target.path, &self.monitors, target.format, self.config.minimal_hyprland_config)
+            } else {
+                Monitor::save_config(&target.path, &self.monitors, target.format, self.config.minimal_hyprland_config)
+            };
+            match saved {
+                Ok(_) => report.push(format!("Wrote {}", target.path)),
+                Err(e) => {
+                    logger::error(&format!("Failed to save {}: {}", target.path, e));
+                    report.push(format!("Could not write {}: {}", target.path, e));
+                    failed = true;
+                },
+            }
+        }
+        if failed {
+            let setting = if self.config.output_targets.is_empty() { "monitors_config_path" } else { "output_targets" };
+            let config = self.config.path.as_ref()
+                .map(|path| path.display().to_string())
+                .unwrap_or_else(|| "config.json".to_string());
+            report.push(format!("Check {} in {}", setting, config));
+            self.popup = Some(Popup::new("Write failed", report));
+            return false;
+        }
+        if targets.len() > 1 {
+            self.popup = Some(Popup::new("Saved", report));
+        }
+        self.config_modified = utils::modified_time(&self.config.monitors_config_path);
+
+        match self.config.save_monitor_state(&self.monitors) {
+            Ok(_) => logger::debug("Monitor state saved"),
+            Err(e) => logger::warn(&format!("Failed to save monitor state: {}", e)),
+        }
+        self.saved_monitors = Some(self.monitors.clone());
+        true
     }         
+
+    // Only checked when the file existed at load, an unknown modification
+    // time has nothing to compare with. A deleted file has nothing to lose.
+    fn changed_on_disk(&self) -> bool {
+        let modified = utils::modified_time(&self.config.monitors_config_path);
+        self.config_modified.is_some() && modified.is_some() && modified != self.config_modified
+    }
+
+    // Takes the monitor lines of the file changed on disk, then `w` writes
+    // them back with the changes made since on the other monitors.
+    fn reload_monitors_config(&mut self) {
+        let path = &self.config.monitors_config_path;
+        let Some(lines) = Monitor::load_hyprland_config(path) else {
+            self.popup = Some(Popup::new("Reload failed", vec![format!("Could not read {}", path)]));
+            return;
+        };
+        for monitor in &mut self.monitors {
+            if let Some(line) = lines.iter().find(|l| l.name == monitor.name) {
+                monitor.apply_hyprland_config(line);
+            }
+        }
+        self.config_modified = utils::modified_time(path);
+    }
+
+    // Replaces only the selected monitor's line in the Hyprland config.
+    fn write_selected(&mut self) {
+        let monitor = &self.monitors[self.selected_monitor];
+        let path = &self.config.monitors_config_path;
+        if self.config.split_monitor_files {
+            let include = Monitor::include_path(path, &monitor.name);
+            self.popup = Some(match monitor.save_include(path, self.config.hyprland_format(), self.config.minimal_hyprland_config) {
+                Ok(_) => Popup::new("Saved", vec![format!("{} written to {}", monitor.name, include.display())]),
+                Err(e) => {
+                    logger::error(&format!("Failed to save {}: {}", include.display(), e));
+                    Popup::new("Write failed", vec![format!("Could not write {}: {}", include.display(), e)])
+                },
+            });
+            return;
+        }
+        if self.config.hyprland_monitorv2 {
+            self.popup = Some(Popup::new("Write failed", vec![
+                "Writing a single monitor needs `monitor = ...` lines,".to_string(),
+                "save them all with <w> when hyprland_monitorv2 is set".to_string(),
+            ]));
+            return;
+        }
+        self.popup = Some(match monitor.save_config_line(path, self.config.minimal_hyprland_config) {
+            // the other lines were read back, so the file is in sync again
+            Ok(_) => {
+                self.config_modified = utils::modified_time(path);
+                Popup::new("Saved", vec![format!("{} written to {}", monitor.name, path)])
+            },
+            Err(e) => {
+                logger::error(&format!("Failed to save the {} line: {}", monitor.name, e));
+                Popup::new("Write failed", vec![format!("Could not write {}: {}", path, e)])
+            },
+        });
+    }
+
+    // Returns false, with the error shown in a popup, if hyprctl failed.
+    fn apply(&mut self) -> bool {
+        match Monitor::apply_hyprland(&self.runner, &self.monitors) {
+            Ok(_) => {
+                self.applied_monitors = Some(self.monitors.clone());
+                true
+            },
+            Err(e) => {
+                logger::error(&format!("Failed to apply monitor configuration: {}", e));
+                self.popup = Some(Popup::new("Apply failed", vec![format!("hyprctl: {}", e)]));
+                false
+            },
+        }
+    }
+
+    fn write_and_apply(&mut self) -> bool {
+        self.write() && self.apply()
+    }
+
+    // End to end check: writes the config, has Hyprland reload it and
+    // detects the monitors again to report what it didn't take.
+    fn write_and_verify(&mut self) {
+        if !self.write() {
+            return;
+        }
+        if let Err(e) = self.runner.run("hyprctl", &["reload".to_string()]) {
+            logger::error(&format!("Failed to run hyprctl reload: {}", e));
+            self.popup = Some(Popup::new("Verify failed", vec![format!("hyprctl: {}", e)]));
+            return;
+        }
+        std::thread::sleep(self.verify_delay);
+        let stdout = match self.runner.run("wlr-randr", &["--json".to_string()]) {
+            Ok(stdout) => stdout,
+            Err(e) => {
+                logger::error(&format!("Failed to run wlr-randr: {}", e));
+                self.popup = Some(Popup::new("Verify failed", vec![format!("wlr-randr: {}", e)]));
+                return;
+            },
+        };
+        let live = Monitor::parse_monitors(&stdout);
+        let lines = Monitor::verify_live(&self.monitors, &live);
+        if lines.is_empty() {
+            self.applied_monitors = Some(self.monitors.clone());
+            self.popup = Some(Popup::new("Verified", vec!["Hyprland runs the written config".to_string()]));
+        } else {
+            logger::warn(&format!("Config not fully applied: {}", lines.join("; ")));
+            self.popup = Some(Popup::new("Not applied", lines));
+        }
+    }
+
+    // Stays open when the write or the apply failed so the error can be read.
+    fn write_apply_and_quit(&mut self) {
+        if self.write_and_apply() {
+            self.exit();
+        }
+    }
 }
 
 impl Widget for &App {
 
     fn render(self,area: Rect, buf: &mut Buffer) {
+        if self.detection.is_some() {
+            self.render_detecting(area, buf);
+            return;
+        }
         let mut monitor_list = MonitorList::new(
             &self.monitors,
             self.mode,
             Some(self.selected_monitor), 
-        );
+        ).with_coordinates(self.coordinates)
+            .with_compact(self.config.compact_list);
+        let mut status = vec![];
+        if self.mode == TUIMode::Move {
+            status.push(format!("moves in {} px", self.coordinates.unit()));
+        }
+        if self.unsaved() {
+            status.push("unsaved changes".to_string());
+        }
+        if self.unapplied() {
+            status.push("unapplied changes".to_string());
+        }
+        if let Some(started) = self.started {
+            status.push(utils::format_clock(SystemTime::now()));
+            status.push(format!("session {}", utils::format_duration(started.elapsed())));
+        }
+        if !status.is_empty() {
+            monitor_list = monitor_list.with_status(status.join(" · "));
+        }
 
         let canvas = Map {
             mode: self.mode,
             selected: self.selected_monitor,
             monitors: &self.monitors,
+            grid: self.show_grid,
+            viewport: self.viewport,
+            margin: self.config.canvas_margin,
+            preview_scale: self.scale_preview.filter(|_| self.mode == TUIMode::Scale),
+            pulse: self.config.pulse_selection && self.frame % 2 == 1,
+            label_align: self.config.label_align,
+            colors: self.colors,
+            footprint: self.show_footprint,
+        };
+
+        let warnings = Monitor::layout_warnings(&self.monitors);
+        let note = Monitor::mixed_scale_note(&self.monitors)
+            .filter(|_| !self.cursor_note_dismissed);
+        let area = if warnings.is_empty() && note.is_none() {
+            area
+        } else {
+            let footer_layout = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints(vec![
+                    Constraint::Min(0),
+                    Constraint::Length(1),
+                ])
+                .split(area);
+            self.render_footer(footer_layout[1], &warnings, note, buf);
+            footer_layout[0]
+        };
+
+        let (top_area, list_area) = match self.layout {
+            LayoutPreset::MapAndList => {
+                let outer_layout = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints(vec![
+                        Constraint::Percentage(70),
+                        Constraint::Percentage(30),
+                    ])
+                    .split(area);
+                (Some(outer_layout[0]), Some(outer_layout[1]))
+            }
+            LayoutPreset::MapOnly => (Some(area), None),
+            LayoutPreset::ListOnly => (None, Some(area)),
+        };
+
+        if let Some(top_area) = top_area {
+            self.render_top(top_area, canvas, buf);
+        }
+        if let Some(list_area) = list_area {
+            monitor_list.render(list_area, buf);
+        }
+        if let Some(popup) = &self.popup {
+            popup.render(area, buf);
+        }
+    }
+}
+
+impl App {
+    // Warnings first, the note is only shown when there are none.
+    fn render_footer(&self, area: Rect, warnings: &[String], note: Option<String>, buf: &mut Buffer) {
+        let line = match note {
+            Some(note) if warnings.is_empty() => Line::from(vec![
+                "  ".blue().bold(),
+                note.blue(),
+                " <X> to dismiss".blue().bold(),
+            ]),
+            _ => Line::from(vec![
+                "  ".yellow().bold(),
+                warnings.join(" | ").yellow(),
+            ]),
         };
-        let outer_layout = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints(vec![
-                Constraint::Percentage(70),
-                Constraint::Percentage(30),
-            ])
-            .split(area);
+        line.render(area, buf);
+    }
 
+    fn render_detecting(&self, area: Rect, buf: &mut Buffer) {
+        let spinner = SPINNER_FRAMES[self.frame as usize % SPINNER_FRAMES.len()];
+        let block = Block::bordered()
+            .title(Line::from(" Display TUI ".bold()).centered());
+        let inner = block.inner(area);
+        block.render(area, buf);
+        let line_area = Rect { y: inner.y + inner.height / 2, height: inner.height.min(1), ..inner };
+        Paragraph::new(Line::from(vec![
+            spinner.yellow().bold(),
+            " Detecting displays with wlr-randr".white(),
+        ]))
+            .centered()
+            .render(line_area, buf);
+    }
+
+    fn render_top(&self, area: Rect, canvas: Map, buf: &mut Buffer) {
         match self.mode {
             TUIMode::Resolution=> {
                 let selected = &self.monitors[self.selected_monitor];
                 let mut resolutions = Resolutions::new(
                         selected,
                         Some(self.selected_resolution)
-                );    
+                ).with_round_refresh(self.config.round_refresh)
+                    .with_pinned(&self.config.pinned_modes);
                 let inner_top_layout = Layout::default()
                     .direction(Direction::Horizontal)
                     .constraints(vec![
                         Constraint::Percentage(70),
                         Constraint::Percentage(30),
                     ])
-                    .split(outer_layout[0]);
+                    .split(area);
                 canvas.render(inner_top_layout[0], buf);
                 resolutions.render(inner_top_layout[1], buf);
             }
@@ -170,22 +784,68 @@ impl Widget for &App {
                         Constraint::Percentage(90),
                         Constraint::Percentage(10),
                     ])
-                    .split(outer_layout[0]);
+                    .split(area);
                 canvas.render(inner_top_layout[0], buf);
                 scale.render(inner_top_layout[1], buf);
             }
+            TUIMode::Settings => {
+                let mut settings = Settings::new(
+                    &self.monitors[self.selected_monitor],
+                    self.selected_setting,
+                    self.editing_text.as_deref(),
+                );
+                let inner_top_layout = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(vec![
+                        Constraint::Percentage(70),
+                        Constraint::Percentage(30),
+                    ])
+                    .split(area);
+                canvas.render(inner_top_layout[0], buf);
+                settings.render(inner_top_layout[1], buf);
+            }
+            TUIMode::Arrange => {
+                let mut arrange = Arrange::new(self.selected_arrangement);
+                let inner_top_layout = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(vec![
+                        Constraint::Percentage(85),
+                        Constraint::Percentage(15),
+                    ])
+                    .split(area);
+                canvas.render(inner_top_layout[0], buf);
+                arrange.render(inner_top_layout[1], buf);
+            }
+            TUIMode::Config => {
+                let mut editor = ConfigEditor::new(
+                    &self.config,
+                    self.selected_config_field,
+                    self.editing_text.as_deref(),
+                );
+                let inner_top_layout = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(vec![
+                        Constraint::Percentage(60),
+                        Constraint::Percentage(40),
+                    ])
+                    .split(area);
+                canvas.render(inner_top_layout[0], buf);
+                editor.render(inner_top_layout[1], buf);
+            }
             _ => {
-                canvas.render(outer_layout[0], buf);
+                canvas.render(area, buf);
             }
         }
-        monitor_list.render(outer_layout[1], buf);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_utils::tests::test_monitors;
+    use std::rc::Rc;
+    use crate::test_utils::tests::FakeRunner;
+    use crate::configuration::OutputTarget;
+    use crate::test_utils::tests::{row_text, test_config, test_monitors};
    
     #[test]
     fn handle_mode_view_key_event() -> io::Result<()> {
@@ -288,7 +948,7 @@ mod tests {
 
         app.handle_key_event(KeyCode::Char(' ').into());
         let monitor = app.monitors[0].clone();
-        assert_eq!(monitor.modes[0].current, true);
+        assert!(monitor.modes[0].current);
 
         app.handle_key_event(KeyCode::Char('q').into());
         assert!(app.exit);
@@ -324,131 +984,1851 @@ mod tests {
         Ok(())
     }       
     #[test]
-    fn handle_mode_view_arrow_key_event() -> io::Result<()> {
+    fn scale_all_key_event() -> io::Result<()> {
+        let mut monitors = test_monitors();
+        monitors[1].enabled = true;
+        monitors.push(Monitor {
+            name: "Monitor 3".to_string(),
+            position: Some(monitor::Position { x: 0, y: 1080 }),
+            ..monitors[0].clone()
+        });
+        monitors[0].scale = Some(2.0);
         let mut app = App{
-            monitors: test_monitors(),
+            monitors,
             selected_monitor: 0,
             ..Default::default()
         };
 
-        app.handle_key_event(KeyCode::Up.into());
-        assert_eq!(app.selected_monitor, 1);
+        app.handle_key_event(KeyCode::Char('S').into());
+        let scales: Vec<Option<f32>> = app.monitors.iter().map(|m| m.scale).collect();
+        assert_eq!(scales, vec![Some(2.0), Some(2.0), Some(2.0)]);
+        assert!(app.popup.is_none());
 
-        app.handle_key_event(KeyCode::Down.into());
-        assert_eq!(app.selected_monitor, 0);
+        // 1280x720 at 1.6 is 800x450, 1920x1080 is 1200x675: all whole pixels
+        app.monitors[0].scale = Some(1.6);
+        app.handle_key_event(KeyCode::Char('S').into());
+        assert!(app.popup.is_none());
 
-        app.handle_key_event(KeyCode::Down.into());
-        assert_eq!(app.selected_monitor, app.monitors.len() - 1);
+        // 1920x1080 at 1.75 isn't
+        app.monitors[0].scale = Some(1.75);
+        app.handle_key_event(KeyCode::Char('S').into());
+        let popup = app.popup.clone().expect("No warning popup");
+        assert_eq!(popup.lines.len(), 4, "{:?}", popup.lines);
 
-        app.handle_key_event(KeyCode::Up.into());
-        assert_eq!(app.selected_monitor, 0);
-        
         Ok(())
     }
 
     #[test]
-    fn handle_mode_move_arrow_key_event() -> io::Result<()> {
+    fn select_neighbor_key_event() -> io::Result<()> {
+        // L shape: Monitor 1 top left, Monitor 2 to its right, Monitor 3 below it
+        let mut monitors = test_monitors();
+        monitors[1].enabled = true;
+        monitors.push(Monitor {
+            name: "Monitor 3".to_string(),
+            position: Some(monitor::Position { x: 0, y: 1080 }),
+            ..monitors[0].clone()
+        });
         let mut app = App{
-            monitors: test_monitors(),
+            monitors,
             selected_monitor: 0,
             ..Default::default()
         };
- 
-        app.handle_key_event(KeyCode::Char('m').into());
-        assert_eq!(app.mode, TUIMode::Move);
-
-        // Shift+Up moves -10
-        app.handle_key_event(KeyEvent{
-            code: KeyCode::Up,
-            modifiers: KeyModifiers::SHIFT,
-            kind: KeyEventKind::Press,
-            state: event::KeyEventState::empty(),
-        });
-        let monitor = app.monitors[app.selected_monitor].clone();
-        assert_eq!(monitor.position.unwrap().y, -10);
-
-        // Shift+Down moves +10
-        app.handle_key_event(KeyEvent{
-             code: KeyCode::Down,
-             modifiers: KeyModifiers::SHIFT,
-             kind: KeyEventKind::Press,
-             state: event::KeyEventState::empty(),
-        });
-        let monitor = app.monitors[app.selected_monitor].clone();
-        assert_eq!(monitor.position.unwrap().y, 0);
-
-        // Shift+Left moves -10
-        app.handle_key_event(KeyEvent{
-             code: KeyCode::Left,
-             modifiers: KeyModifiers::SHIFT,
-             kind: KeyEventKind::Press,
-             state: event::KeyEventState::empty(),
-        });
-        let monitor = app.monitors[app.selected_monitor].clone();
-        assert_eq!(monitor.position.unwrap().x, -10);
-
-        // Shift+Right moves +10
-        app.handle_key_event(KeyEvent{
-             code: KeyCode::Right,
-             modifiers: KeyModifiers::SHIFT,
-             kind: KeyEventKind::Press,
-             state: event::KeyEventState::empty(),
-        });
-        let monitor = app.monitors[app.selected_monitor].clone();
-        assert_eq!(monitor.position.unwrap().x, 0);
+        let alt = |c: char| KeyEvent::new(KeyCode::Char(c), KeyModifiers::ALT);
 
-
-        // Snap Test
-        // Move to 100
-        app.monitors[app.selected_monitor].position.as_mut().unwrap().y = 100;
-        
-        // Up (no shift) should snap to 0 (which is a generic target for all monitors)
-        app.handle_key_event(KeyCode::Up.into());
-        let monitor = app.monitors[app.selected_monitor].clone();
-        assert_eq!(monitor.position.unwrap().y, 0);
+        app.handle_key_event(alt('j'));
+        assert_eq!(app.selected_monitor, 2);
+        // nothing further down
+        app.handle_key_event(alt('j'));
+        assert_eq!(app.selected_monitor, 2);
+        app.handle_key_event(alt('l'));
+        assert_eq!(app.selected_monitor, 1);
+        app.handle_key_event(alt('h'));
+        assert_eq!(app.selected_monitor, 0);
+        // Monitor 2 is a bit higher, but mostly to the right
+        app.handle_key_event(alt('k'));
+        assert_eq!(app.selected_monitor, 0);
+        app.handle_key_event(alt('l'));
+        assert_eq!(app.selected_monitor, 1);
+        // Monitor 3 is further left than below
+        app.handle_key_event(alt('j'));
+        assert_eq!(app.selected_monitor, 1);
 
         Ok(())
     }
 
     #[test]
-    fn handle_mode_resolution_arrow_key_event() -> io::Result<()> {
+    fn locked_monitor_ignores_edits() -> io::Result<()> {
         let mut app = App{
             monitors: test_monitors(),
             selected_monitor: 0,
             ..Default::default()
         };
 
-        app.handle_key_event(KeyCode::Char('r').into());
-        assert_eq!(app.mode, TUIMode::Resolution);
+        app.handle_key_event(KeyCode::Char('L').into());
+        assert!(app.monitors[0].locked);
 
-        app.selected_resolution = 0;
-        app.handle_key_event(KeyCode::Down.into());
-        assert_eq!(app.selected_resolution, 1);
+        app.handle_key_event(KeyCode::Char('m').into());
+        for key in "LJlhj".chars() {
+            app.handle_key_event(KeyCode::Char(key).into());
+        }
+        assert_eq!(app.monitors[0].position, Some(monitor::Position { x: 0, y: 0 }));
+        app.handle_key_event(KeyCode::Esc.into());
+        assert_eq!(app.mode, TUIMode::View);
 
-        app.handle_key_event(KeyCode::Up.into());
-        assert_eq!(app.selected_resolution, 0);
+        app.handle_key_event(KeyCode::Char('s').into());
+        app.handle_key_event(KeyCode::Char(' ').into());
+        app.handle_key_event(KeyCode::Char('+').into());
+        assert_eq!(app.monitors[0].scale, Some(1.0));
+        app.handle_key_event(KeyCode::Esc.into());
 
-        Ok(())
-    }
+        app.handle_key_event(KeyCode::Char('r').into());
+        app.handle_key_event(KeyCode::Char('j').into());
+        app.handle_key_event(KeyCode::Char(' ').into());
+        assert_eq!(app.monitors[0].get_current_resolution().unwrap().width, 1920);
+        app.handle_key_event(KeyCode::Esc.into());
+
+        // unlocked again, edits go through
+        app.handle_key_event(KeyCode::Char('L').into());
+        app.handle_key_event(KeyCode::Char('m').into());
+        app.handle_key_event(KeyCode::Char('L').into());
+        assert_eq!(app.monitors[0].position, Some(monitor::Position { x: 10, y: 0 }));
+
+        Ok(())
+    }
+
+    #[test]
+    fn live_move_throttles_applies() {
+        let runner = Rc::new(FakeRunner::default());
+        let monitors = test_monitors();
+        let mut app = App{
+            applied_monitors: Some(monitors.clone()),
+            monitors,
+            config: Configuration {
+                live_move: true,
+                ..Default::default()
+            },
+            runner: Runner(runner.clone()),
+            ..Default::default()
+        };
+        let before = app.monitors.clone();
+
+        app.handle_key_event(KeyCode::Char('m').into());
+        let start = Instant::now();
+        // twenty moves 10ms apart, applied at 0 and 100ms only
+        for i in 0..20 {
+            app.handle_key_event(KeyCode::Char('L').into());
+            app.live_apply(start + Duration::from_millis(i * 10));
+        }
+        assert_eq!(runner.calls.borrow().len(), 2);
+        assert!(app.unapplied());
+
+        // confirming applies the last move
+        app.handle_key_event(KeyCode::Enter.into());
+        assert_eq!(runner.calls.borrow().len(), 3);
+        assert!(!app.unapplied());
+        assert_eq!(app.mode, TUIMode::View);
+        let moved = app.monitors.clone();
+        assert_ne!(moved, before);
+
+        // Esc puts the monitors back and applies them
+        app.handle_key_event(KeyCode::Char('m').into());
+        app.handle_key_event(KeyCode::Char('L').into());
+        app.live_apply(start + Duration::from_secs(1));
+        assert_eq!(runner.calls.borrow().len(), 4);
+        app.handle_key_event(KeyCode::Esc.into());
+        assert_eq!(runner.calls.borrow().len(), 5);
+        assert_eq!(app.monitors, moved);
+        assert_eq!(app.mode, TUIMode::View);
+    }
+
+    #[test]
+    fn write_output_targets() -> io::Result<()> {
+        let hyprland = std::env::temp_dir().join("display-tui-targets-monitors.conf");
+        let sway = std::env::temp_dir().join("display-tui-targets-outputs");
+        let target = |format, path: &std::path::Path| OutputTarget { format, path: path.to_str().unwrap().to_string() };
+        let mut app = App{
+            monitors: test_monitors(),
+            config: Configuration {
+                output_targets: vec![
+                    target(utils::ConfigFormat::Hyprland, &hyprland),
+                    target(utils::ConfigFormat::Sway, &sway),
+                    target(utils::ConfigFormat::Sway, std::path::Path::new("/nonexistent/display-tui/outputs")),
+                ],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // the unwritable target fails, the others are still written
+        assert!(!app.write());
+        let popup = app.popup.take().expect("No report");
+        assert_eq!(popup.title, "Write failed");
+        assert_eq!(popup.lines[0], format!("Wrote {}", hyprland.display()));
+        assert!(popup.lines[2].starts_with("Could not write /nonexistent/display-tui/outputs"), "{:?}", popup.lines);
+
+        app.config.output_targets.pop();
+        assert!(app.write());
+        assert_eq!(app.popup.take().expect("No report").title, "Saved");
+
+        let expected_hyprland: Vec<String> = app.monitors.iter().map(|m| m.to_hyprland_config(false)).collect();
+        let expected_sway: Vec<String> = app.monitors.iter().map(|m| m.to_sway_config()).collect();
+        assert_eq!(std::fs::read_to_string(&hyprland)?, expected_hyprland.join("\n") + "\n");
+        assert_eq!(std::fs::read_to_string(&sway)?, expected_sway.join("\n") + "\n");
+        Ok(())
+    }
+
+    #[test]
+    fn reconcile_key_event() -> io::Result<()> {
+        // the compositor kept Monitor 1 at 1280x720
+        let stdout = r#"[{"name": "Monitor 1", "enabled": true, "position": {"x": 0, "y": 0}, "scale": 1.0, "modes": [
+            {"width": 1920, "height": 1080, "refresh": 60.0, "preferred": true, "current": false},
+            {"width": 1280, "height": 720, "refresh": 60.0, "preferred": false, "current": true}
+        ]}]"#;
+        let runner = Rc::new(FakeRunner::with_output("wlr-randr", stdout));
+        let mut app = App{
+            monitors: test_monitors(),
+            runner: Runner(runner.clone()),
+            ..Default::default()
+        };
+
+        app.handle_key_event(KeyCode::Char('R').into());
+
+        assert_eq!(runner.calls.borrow()[0].0, "wlr-randr");
+        let popup = app.popup.take().expect("No report");
+        assert_eq!(popup.title, "Reconcile");
+        assert_eq!(popup.lines, vec!["Monitor 1: selected 1920x1080@60, running 1280x720@60".to_string()]);
+        assert_eq!(app.monitors[0].get_current_resolution().map(|m| m.width), Some(1280));
+
+        // nothing left to report
+        app.handle_key_event(KeyCode::Char('R').into());
+        assert_eq!(app.popup.take().expect("No report").lines, vec!["Every selected mode is running".to_string()]);
+        Ok(())
+    }
+
+    #[test]
+    fn write_and_verify_key_event() {
+        let path = std::env::temp_dir().join("display-tui-verify.conf");
+        let config = Configuration {
+            monitors_config_path: path.to_str().unwrap().to_string(),
+            ..Default::default()
+        };
+        let live = |mode_1280: bool| format!(r#"[
+            {{"name": "Monitor 1", "enabled": true, "modes": [
+                {{"width": 1920, "height": 1080, "refresh": 60.0, "preferred": true, "current": {}}},
+                {{"width": 1280, "height": 720, "refresh": 60.0, "preferred": false, "current": {}}}
+            ], "position": {{"x": 0, "y": 0}}, "transform": "normal", "scale": 1.0}},
+            {{"name": "Monitor 2", "enabled": false, "modes": []}}
+        ]"#, !mode_1280, mode_1280);
+
+        // Hyprland runs what was written
+        let runner = Rc::new(FakeRunner::with_output("wlr-randr", &live(false)));
+        let mut app = App{
+            monitors: test_monitors(),
+            config: config.clone(),
+            runner: Runner(runner.clone()),
+            ..Default::default()
+        };
+        app.handle_key_event(KeyCode::Char('V').into());
+        let programs: Vec<String> = runner.calls.borrow().iter().map(|(p, _)| p.clone()).collect();
+        assert_eq!(programs, vec!["hyprctl", "wlr-randr"]);
+        assert_eq!(runner.calls.borrow()[0].1, vec!["reload".to_string()]);
+        assert_eq!(app.popup.take().expect("No report").title, "Verified");
+        assert_eq!(app.applied_monitors.as_ref(), Some(&app.monitors));
+
+        // the mode was silently rejected
+        let runner = Rc::new(FakeRunner::with_output("wlr-randr", &live(true)));
+        let mut app = App{
+            monitors: test_monitors(),
+            config,
+            runner: Runner(runner.clone()),
+            ..Default::default()
+        };
+        app.handle_key_event(KeyCode::Char('V').into());
+        let popup = app.popup.take().expect("No report");
+        assert_eq!(popup.title, "Not applied");
+        assert_eq!(popup.lines, vec!["Monitor 1: mode 1920x1080@60, running 1280x720@60".to_string()]);
+    }
+
+    #[test]
+    fn write_changed_on_disk() -> io::Result<()> {
+        let config = test_config("changed-on-disk");
+        let path = std::path::PathBuf::from(&config.monitors_config_path);
+        let mut app = App{
+            monitors: test_monitors(),
+            config,
+            ..Default::default()
+        };
+        assert!(app.write());
+        let written = std::fs::read_to_string(&path)?;
+
+        // edited in another editor, with a later modification time
+        let external = "monitor = Monitor 1, 1280x720@60, 0x0, 1\n";
+        std::fs::write(&path, external)?;
+        let file = std::fs::File::options().write(true).open(&path)?;
+        file.set_modified(SystemTime::now() + Duration::from_secs(60))?;
+
+        app.handle_key_event(KeyCode::Char('w').into());
+        assert_eq!(app.popup.clone().expect("No warning").title, "Changed on disk");
+        assert_eq!(std::fs::read_to_string(&path)?, external);
+
+        // r takes the file's monitors, then w writes without asking
+        app.handle_key_event(KeyCode::Char('r').into());
+        assert!(app.popup.is_none());
+        assert_eq!(app.monitors[0].get_current_resolution().map(|m| m.width), Some(1280));
+        app.handle_key_event(KeyCode::Char('w').into());
+        assert!(app.popup.is_none());
+        assert_ne!(std::fs::read_to_string(&path)?, written);
+        assert!(std::fs::read_to_string(&path)?.contains("1280x720@60"));
+
+        // y resumes the key that asked: Z writes, applies and quits
+        std::fs::write(&path, external)?;
+        let file = std::fs::File::options().write(true).open(&path)?;
+        file.set_modified(SystemTime::now() + Duration::from_secs(120))?;
+        let runner = Rc::new(FakeRunner::default());
+        app.runner = Runner(runner.clone());
+        app.handle_key_event(KeyCode::Char('Z').into());
+        assert_eq!(app.prompt, Some(Prompt::Overwrite(WriteAction::ApplyAndQuit)));
+        assert!(!app.exit);
+        app.handle_key_event(KeyCode::Char('y').into());
+        assert!(app.exit);
+        assert!(!runner.calls.borrow().is_empty());
+        assert_ne!(std::fs::read_to_string(&path)?, external);
+        Ok(())
+    }
+
+    #[test]
+    fn write_error_shows_popup() -> io::Result<()> {
+        let runner = Rc::new(FakeRunner::default());
+        let mut app = App{
+            monitors: test_monitors(),
+            config: Configuration {
+                path: Some(std::path::PathBuf::from("/tmp/profiles/work.json")),
+                monitors_config_path: "/nonexistent/display-tui/monitors.conf".to_string(),
+                ..Default::default()
+            },
+            runner: Runner(runner.clone()),
+            ..Default::default()
+        };
+
+        app.handle_key_event(KeyCode::Char('Z').into());
+
+        assert!(!app.exit);
+        assert!(runner.calls.borrow().is_empty());
+        let popup = app.popup.clone().expect("No error popup");
+        assert_eq!(popup.title, "Write failed");
+        assert!(popup.lines[0].contains("/nonexistent/display-tui/monitors.conf"), "{:?}", popup.lines);
+        assert_eq!(popup.lines.last().unwrap(), "Check monitors_config_path in /tmp/profiles/work.json");
+
+        // dismissed by any key
+        app.handle_key_event(KeyCode::Char('x').into());
+        assert!(app.popup.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn disabled_monitor_blocks_edit_modes_key_event() -> io::Result<()> {
+        let mut app = App{
+            monitors: test_monitors(),
+            selected_monitor: 1,
+            ..Default::default()
+        };
+
+        for key in ['r', 's'] {
+            app.handle_key_event(KeyCode::Char(key).into());
+            assert_eq!(app.mode, TUIMode::View);
+            let popup = app.popup.take().expect("No disabled popup");
+            assert_eq!(popup.title, "Disabled");
+        }
+
+        app.handle_key_event(KeyCode::Char('e').into());
+        app.handle_key_event(KeyCode::Char('r').into());
+        assert_eq!(app.mode, TUIMode::Resolution);
+        assert!(app.popup.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn copy_config_line_key_event() -> io::Result<()> {
+        let runner = Rc::new(FakeRunner::default());
+        let mut app = App{
+            monitors: test_monitors(),
+            runner: Runner(runner.clone()),
+            ..Default::default()
+        };
+
+        app.handle_key_event(KeyCode::Char('y').into());
+
+        let line = "monitor = Monitor 1, 1920x1080@60, 0x0, 1, transform,0".to_string();
+        assert_eq!(*runner.calls.borrow(), vec![("wl-copy".to_string(), vec![line.clone()])]);
+        let popup = app.popup.clone().expect("No copy popup");
+        assert_eq!(popup.title, "Copied");
+        assert_eq!(popup.lines, vec![line]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fit_map_key_event() -> io::Result<()> {
+        let mut app = App{
+            monitors: test_monitors(),
+            ..Default::default()
+        };
+        let area = Rect::new(0, 0, 100, 30);
+        let fitted = Monitor::get_monitors_canvas(&app.monitors, &area, app.config.canvas_margin);
+
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('l'), KeyModifiers::CONTROL));
+        app.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::CONTROL));
+        assert_eq!(app.viewport, Viewport { pan_x: 0.1, pan_y: 0.1 });
+        let mut panned = Monitor::get_monitors_canvas(&app.monitors, &area, app.config.canvas_margin);
+        app.viewport.apply(&mut panned);
+        assert!(panned.x_bounds[0] > fitted.x_bounds[0]);
+        assert!(panned.y_bounds[0] > fitted.y_bounds[0]);
+
+        app.handle_key_event(KeyCode::Char('f').into());
+        assert_eq!(app.viewport, Viewport::default());
+        let mut restored = Monitor::get_monitors_canvas(&app.monitors, &area, app.config.canvas_margin);
+        app.viewport.apply(&mut restored);
+        assert_eq!(restored.x_bounds, fitted.x_bounds);
+        assert_eq!(restored.y_bounds, fitted.y_bounds);
+
+        Ok(())
+    }
+
+    #[test]
+    fn handle_event_batch() -> io::Result<()> {
+        let mut monitors = test_monitors();
+        monitors.push(monitors[0].clone());
+        let mut app = App{
+            monitors,
+            ..Default::default()
+        };
+        let release = KeyEvent::new_with_kind(KeyCode::Char('j'), KeyModifiers::NONE, KeyEventKind::Release);
+        let mut queue: std::collections::VecDeque<Event> = [
+            Event::Key(KeyCode::Char('j').into()),
+            Event::Key(release),
+            Event::Key(KeyCode::Char('j').into()),
+            Event::FocusGained,
+            Event::Key(KeyCode::Char('k').into()),
+        ].into();
+
+        app.handle_event_batch(|| Ok(queue.pop_front()))?;
+
+        // releases and other events are ignored
+        assert_eq!(app.selected_monitor, 1);
+        assert!(queue.is_empty());
+
+        // events queued after quitting are left alone
+        queue.extend([Event::Key(KeyCode::Char('q').into()), Event::Key(KeyCode::Char('j').into())]);
+        app.handle_event_batch(|| Ok(queue.pop_front()))?;
+        assert!(app.exit);
+        assert_eq!(app.selected_monitor, 1);
+        assert_eq!(queue.len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn monitors_from_file() -> io::Result<()> {
+        let path = std::env::temp_dir().join("display-tui-monitors-from-test.json");
+        std::fs::write(&path, r#"[
+            {"name": "DEMO-1", "enabled": true, "modes": [{"width": 2560, "height": 1440, "refresh": 144.0, "preferred": true, "current": true}], "position": {"x": 0, "y": 0}, "scale": 1.0},
+            {"name": "DEMO-2", "enabled": false, "modes": [{"width": 1920, "height": 1080, "refresh": 60.0, "preferred": true, "current": false}], "position": {"x": 2560, "y": 0}, "scale": 1.0}
+        ]"#)?;
+
+        let runner = Runner::with_monitors_from(path.to_str().unwrap())?;
+        let mut app = App::load(runner, test_config("monitors-from"));
+
+        let names: Vec<&str> = app.monitors.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["DEMO-1", "DEMO-2"]);
+        assert_eq!(app.monitors[1].get_current_resolution().map(|m| m.width), Some(1920));
+        // still interactive
+        app.handle_key_event(KeyCode::Char('j').into());
+        assert_eq!(app.selected_monitor, 1);
+
+        assert!(Runner::with_monitors_from("/nonexistent/display-tui.json").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn config_editor_key_event() -> io::Result<()> {
+        let path = std::env::temp_dir().join("display-tui-config-editor-test.json");
+        let monitors_path = std::env::temp_dir().join("display-tui-edited.conf");
+        let mut app = App{
+            monitors: test_monitors(),
+            config: Configuration {
+                path: Some(path.clone()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        app.handle_key_event(KeyCode::Char('C').into());
+        assert_eq!(app.mode, TUIMode::Config);
+
+        // first field: the monitors config path, typed over the current one
+        app.handle_key_event(KeyCode::Enter.into());
+        app.editing_text = Some(String::new());
+        for c in monitors_path.to_str().unwrap().chars() {
+            app.handle_key_event(KeyCode::Char(c).into());
+        }
+        app.handle_key_event(KeyCode::Enter.into());
+        assert_eq!(app.config.monitors_config_path, monitors_path.to_str().unwrap());
+        assert!(app.popup.is_none());
+
+        let loaded = Configuration::parse(&std::fs::read_to_string(&path)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        assert_eq!(loaded.monitors_config_path, monitors_path.to_str().unwrap());
+
+        // an invalid path is refused
+        app.handle_key_event(KeyCode::Enter.into());
+        app.editing_text = Some("/nonexistent/dir/monitors.conf".to_string());
+        app.handle_key_event(KeyCode::Enter.into());
+        assert_eq!(app.config.monitors_config_path, monitors_path.to_str().unwrap());
+        assert_eq!(app.popup.clone().expect("No popup").title, "Config not saved");
+
+        Ok(())
+    }
+
+    #[test]
+    fn aliased_monitor() {
+        let stdout = r#"[
+            {"name": "DP-3", "enabled": true, "modes": [{"width": 1920, "height": 1080, "refresh": 60.0, "preferred": true, "current": true}], "position": {"x": 0, "y": 0}, "scale": 1.0}
+        ]"#;
+        let runner = Runner(Rc::new(FakeRunner::with_output("wlr-randr", stdout)));
+        let config = Configuration {
+            aliases: [("DP-3".to_string(), "Left desk".to_string())].into(),
+            ..test_config("aliased-monitor")
+        };
+        let app = App::load(runner, config);
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 110, 30));
+        (&app).render(buf.area, &mut buf);
+        let list_row = row_text(&buf, 24);
+        assert!(list_row.contains("Left desk"), "{}", list_row);
+        assert!(!list_row.contains("DP-3"), "{}", list_row);
+
+        assert!(app.monitors[0].to_hyprland_config(false).starts_with("monitor = DP-3, "));
+    }
+
+    #[test]
+    fn headless_moves_export() -> io::Result<()> {
+        let stdout = r#"[
+            {"name": "HEADLESS-1", "enabled": true, "modes": [{"width": 1920, "height": 1080, "refresh": 60.0, "preferred": true, "current": true}], "position": {"x": 0, "y": 0}, "scale": 1.0},
+            {"name": "HEADLESS-2", "enabled": true, "modes": [{"width": 2560, "height": 1440, "refresh": 144.0, "preferred": true, "current": true}], "position": {"x": 1920, "y": 0}, "scale": 1.0}
+        ]"#;
+        let runner = Runner(Rc::new(FakeRunner::with_output("wlr-randr", stdout)));
+        let mut app = App::load(runner, test_config("headless-export"));
+
+        // second monitor: 20px right, 10px down, then rotated
+        let keys = cli::parse_keys("jmLLJ<Esc>o").map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        app.handle_keys(&keys);
+
+        let path = std::env::temp_dir().join("display-tui-headless-test.conf");
+        let options = CliOptions {
+            output: Some(path.to_str().unwrap().to_string()),
+            ..Default::default()
+        };
+        cli::export(&options, &app.monitors, &app.config)?;
+        assert_eq!(
+            std::fs::read_to_string(&path)?,
+            "monitor = HEADLESS-1, 1920x1080@60, 0x0, 1, transform,0\n\
+             monitor = HEADLESS-2, 2560x1440@144, 1940x10, 1, transform,1\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn detection_failed() -> io::Result<()> {
+        let detection = MonitorDetection::spawn(|| Err(io::Error::new(io::ErrorKind::NotFound, "wlr-randr not found")));
+        let mut app = App::detecting(Runner::default(), test_config("detection-failed"), detection);
+        app.started = None;
+        while app.detection.is_some() {
+            std::thread::sleep(Duration::from_millis(5));
+            app.finish_detection();
+        }
+
+        assert!(app.monitors.is_empty());
+        assert_eq!(app.popup.as_ref().map(|p| p.title.as_str()), Some("No displays"));
+        let mut buf = Buffer::empty(Rect::new(0, 0, 100, 30));
+        app.render(buf.area, &mut buf);
+
+        // only quitting is left
+        for key in ['x', 'm', 'j', 'd', 'q'] {
+            app.handle_key_event(KeyCode::Char(key).into());
+        }
+        assert!(app.exit);
+        Ok(())
+    }
+
+    #[test]
+    fn detection_spinner_then_monitors() -> io::Result<()> {
+        let stdout = r#"[{"name": "DP-9", "enabled": true, "position": {"x": 0, "y": 0}, "scale": 1.0, "modes": [
+            {"width": 1920, "height": 1080, "refresh": 60.0, "preferred": true, "current": true}
+        ]}]"#;
+        let (release, wait) = std::sync::mpsc::channel::<()>();
+        let detection = MonitorDetection::spawn(move || {
+            wait.recv().ok();
+            Ok(stdout.to_string())
+        });
+        let mut app = App::detecting(Runner::default(), test_config("detection-spinner"), detection);
+        app.started = None;
+
+        let mut timeouts = vec![];
+        app.handle_pending_events(|timeout| { timeouts.push(timeout); Ok(false) }, || Ok(Event::FocusGained))?;
+        assert_eq!(timeouts, vec![SPINNER_INTERVAL]);
+        assert!(app.monitors.is_empty());
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 50, 5));
+        app.render(buf.area, &mut buf);
+        let middle = row_text(&buf, 2);
+        assert!(middle.contains("⠙ Detecting displays"), "{}", middle);
+
+        // keys wait for the monitors
+        app.handle_key_event(KeyCode::Char('m').into());
+        assert_eq!(app.mode, TUIMode::View);
+
+        release.send(()).unwrap();
+        for _ in 0..200 {
+            app.handle_pending_events(|_| Ok(false), || Ok(Event::FocusGained))?;
+            if app.detection.is_none() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        assert!(app.detection.is_none());
+        assert_eq!(app.monitors.len(), 1);
+        assert_eq!(app.monitors[0].name, "DP-9");
+        assert_eq!(app.saved_monitors.as_ref(), Some(&app.monitors));
+        Ok(())
+    }
+
+    #[test]
+    fn handle_pending_events_waits_for_tick() -> io::Result<()> {
+        let mut app = App{
+            monitors: test_monitors(),
+            ..Default::default()
+        };
+        let mut timeouts = vec![];
+        let mut reads = 0;
+
+        // nothing queued: a single wait of a whole tick
+        app.handle_pending_events(|timeout| { timeouts.push(timeout); Ok(false) }, || { reads += 1; Ok(Event::FocusGained) })?;
+        assert_eq!(timeouts, vec![TICK]);
+        assert_eq!(reads, 0);
+
+        // one event: read it, then only check for queued ones
+        timeouts.clear();
+        let mut ready = vec![true];
+        app.handle_pending_events(
+            |timeout| { timeouts.push(timeout); Ok(ready.pop().unwrap_or(false)) },
+            || { reads += 1; Ok(Event::Key(KeyCode::Char('j').into())) },
+        )?;
+        assert_eq!(timeouts, vec![TICK, Duration::ZERO]);
+        assert_eq!(reads, 1);
+        assert_eq!(app.selected_monitor, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn write_selected_key_event() -> io::Result<()> {
+        let path = std::env::temp_dir().join("display-tui-write-selected-test.conf");
+        std::fs::write(&path, "# kept\nmonitor = Monitor 1, preferred, auto, 1\nmonitor = Monitor 2, disabled\n")?;
+        let mut app = App{
+            monitors: test_monitors(),
+            config: Configuration {
+                monitors_config_path: path.to_str().unwrap().to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        app.monitors[1].enable();
+
+        app.handle_key_event(KeyCode::Char('W').into());
+
+        assert_eq!(
+            std::fs::read_to_string(&path)?,
+            "# kept\nmonitor = Monitor 1, 1920x1080@60, 0x0, 1, transform,0\nmonitor = Monitor 2, disabled\n"
+        );
+        let popup = app.popup.clone().expect("No saved popup");
+        assert_eq!(popup.title, "Saved");
+        assert!(popup.lines[0].starts_with("Monitor 1 written to"), "{:?}", popup.lines);
+
+        Ok(())
+    }
+
+    #[test]
+    fn close_gaps_key_event() -> io::Result<()> {
+        let mut monitors = test_monitors();
+        monitors[1].enable();
+        let mut third = monitors[0].clone();
+        third.name = "Monitor 3".to_string();
+        third.position = Some(monitor::Position { x: 2944, y: 0 });
+        monitors.push(third);
+        let mut app = App{
+            monitors,
+            selected_monitor: 1,
+            config: Configuration {
+                close_gaps: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        app.handle_key_event(KeyCode::Char('d').into());
+        assert_eq!(app.monitors[2].position, Some(monitor::Position { x: 1920, y: 0 }));
+        app.handle_key_event(KeyCode::Char('e').into());
+        assert_eq!(app.monitors[2].position, Some(monitor::Position { x: 2944, y: 0 }));
+
+        // off by default
+        app.config.close_gaps = false;
+        app.handle_key_event(KeyCode::Char('d').into());
+        assert_eq!(app.monitors[2].position, Some(monitor::Position { x: 2944, y: 0 }));
+
+        Ok(())
+    }
+
+    #[test]
+    fn presentation_key_event() -> io::Result<()> {
+        let mut app = App{
+            monitors: test_monitors(),
+            selected_monitor: 1,
+            ..Default::default()
+        };
+        app.monitors[1].set_current_resolution(0);
+        let before = format!("{:?}", app.monitors);
+
+        app.handle_key_event(KeyCode::Char('P').into());
+        assert!(!app.monitors[0].enabled);
+        let monitor = &app.monitors[1];
+        assert!(monitor.enabled);
+        assert!(monitor.get_current_resolution().is_some_and(|m| m.preferred));
+        assert_eq!(monitor.scale, Some(1.0));
+        assert_eq!(monitor.position, Some(monitor::Position { x: 0, y: 0 }));
+
+        app.handle_key_event(KeyCode::Char('P').into());
+        assert_eq!(format!("{:?}", app.monitors), before);
+        assert!(app.presentation_snapshot.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn unsaved_and_unapplied_changes() -> io::Result<()> {
+        let path = std::env::temp_dir().join("display-tui-unsaved-test.conf");
+        let runner = Rc::new(FakeRunner::default());
+        let mut app = App{
+            monitors: test_monitors(),
+            config: Configuration {
+                monitors_config_path: path.to_str().unwrap().to_string(),
+                ..Default::default()
+            },
+            runner: Runner(runner.clone()),
+            ..Default::default()
+        };
+        app.saved_monitors = Some(app.monitors.clone());
+        app.applied_monitors = Some(app.monitors.clone());
+        assert!(!app.unsaved() && !app.unapplied());
+
+        app.handle_key_event(KeyCode::Char('m').into());
+        app.handle_key_event(KeyCode::Char('L').into());
+        app.handle_key_event(KeyCode::Esc.into());
+        assert!(app.unsaved() && app.unapplied());
+
+        app.handle_key_event(KeyCode::Char('w').into());
+        assert!(!app.unsaved());
+        assert!(app.unapplied());
+
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL));
+        assert!(!app.unsaved() && !app.unapplied());
+
+        Ok(())
+    }
+
+    #[test]
+    fn scale_preview_key_event() -> io::Result<()> {
+        let mut app = App{
+            monitors: test_monitors(),
+            ..Default::default()
+        };
+
+        app.handle_key_event(KeyCode::Char('s').into());
+        app.handle_key_event(KeyCode::Char('j').into());
+        let hovered = utils::ScaleValue::table()[app.selected_scale].value;
+        assert_eq!(app.scale_preview, Some(hovered));
+        assert_eq!(app.monitors[0].scale, Some(1.0));
+
+        // reverted on Esc
+        app.handle_key_event(KeyCode::Esc.into());
+        assert_eq!(app.scale_preview, None);
+        assert_eq!(app.monitors[0].scale, Some(1.0));
+
+        // committed on space
+        app.handle_key_event(KeyCode::Char('s').into());
+        app.handle_key_event(KeyCode::Char('j').into());
+        let hovered = utils::ScaleValue::table()[app.selected_scale].value;
+        app.handle_key_event(KeyCode::Char(' ').into());
+        assert_eq!(app.scale_preview, None);
+        assert_eq!(app.monitors[0].scale, Some(hovered));
+
+        Ok(())
+    }
+
+    #[test]
+    fn help_key_event() -> io::Result<()> {
+        let mut app = App{
+            monitors: test_monitors(),
+            ..Default::default()
+        };
+
+        app.handle_key_event(KeyCode::Char('?').into());
+
+        let popup = app.popup.clone().expect("No help popup");
+        assert_eq!(popup.title, "Keys");
+        assert!(popup.lines.contains(&" Max Refresh <M> ".to_string()), "{:?}", popup.lines);
+
+        // the key closing the help isn't handled
+        app.handle_key_event(KeyCode::Char('q').into());
+        assert!(app.popup.is_none());
+        assert!(!app.exit);
+
+        Ok(())
+    }
+
+    #[test]
+    fn handle_mode_arrange_key_event() -> io::Result<()> {
+        let mut monitors = test_monitors();
+        monitors[1].enabled = true;
+        monitors[1].position = Some(monitor::Position { x: 200, y: 300 });
+        let mut app = App{
+            monitors,
+            selected_monitor: 0,
+            ..Default::default()
+        };
+
+        app.handle_key_event(KeyCode::Char('a').into());
+        assert_eq!(app.mode, TUIMode::Arrange);
+
+        app.handle_key_event(KeyCode::Char('j').into());
+        assert_eq!(app.selected_arrangement, 1);
+        app.handle_key_event(KeyCode::Char(' ').into());
+        assert_eq!(app.monitors[1].position, Some(monitor::Position { x: 0, y: 1080 }));
+
+        app.handle_key_event(KeyCode::Esc.into());
+        assert_eq!(app.mode, TUIMode::View);
+
+        Ok(())
+    }
+
+    #[test]
+    fn max_refresh_key_event() -> io::Result<()> {
+        let mut monitors = test_monitors();
+        monitors[0].modes = [(1920, 1080, 60.0, true), (2560, 1440, 165.0, false), (1920, 1080, 144.0, false), (1920, 1080, 120.0, false)]
+            .into_iter()
+            .map(|(width, height, refresh, current)| monitor::Resolution { width, height, refresh, preferred: current, current, interlaced: false, custom: false })
+            .collect();
+        let mut app = App{
+            monitors,
+            selected_monitor: 0,
+            ..Default::default()
+        };
+
+        app.handle_key_event(KeyCode::Char('r').into());
+        app.handle_key_event(KeyCode::Char('M').into());
+        let current = app.monitors[0].get_current_resolution().unwrap();
+        assert_eq!((current.width, current.height, current.refresh), (1920, 1080, 144.0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn adjust_scale_key_event() -> io::Result<()> {
+        let mut app = App{
+            monitors: test_monitors(),
+            selected_monitor: 0,
+            ..Default::default()
+        };
+
+        app.handle_key_event(KeyCode::Char('s').into());
+        for _ in 0..3 {
+            app.handle_key_event(KeyCode::Char('-').into());
+        }
+        assert_eq!(app.monitors[0].scale, Some(0.85));
+
+        app.handle_key_event(KeyCode::Char('+').into());
+        assert_eq!(app.monitors[0].scale, Some(0.9));
+
+        app.monitors[0].scale = Some(0.26);
+        app.handle_key_event(KeyCode::Char('-').into());
+        assert_eq!(app.monitors[0].scale, Some(0.25));
+
+        Ok(())
+    }
+
+    #[test]
+    fn wrap_navigation_key_event() -> io::Result<()> {
+        let mut app = App{
+            monitors: test_monitors(),
+            selected_monitor: 0,
+            ..Default::default()
+        };
+
+        app.handle_key_event(KeyCode::Char('r').into());
+        app.handle_key_event(KeyCode::Char('k').into());
+        assert_eq!(app.selected_resolution, 1);
+        app.handle_key_event(KeyCode::Char('j').into());
+        assert_eq!(app.selected_resolution, 0);
+
+        app.config.wrap_navigation = false;
+        app.handle_key_event(KeyCode::Char('k').into());
+        assert_eq!(app.selected_resolution, 0);
+        app.handle_key_event(KeyCode::Char('j').into());
+        app.handle_key_event(KeyCode::Char('j').into());
+        assert_eq!(app.selected_resolution, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn select_primary_key_event() {
+        let mut monitors = test_monitors();
+        monitors[1].enabled = true;
+        let mut app = App{
+            monitors,
+            selected_monitor: 0,
+            ..Default::default()
+        };
+
+        app.monitors[1].primary = true;
+        app.handle_key_event(KeyCode::Char('0').into());
+        assert_eq!(app.selected_monitor, 1);
+
+        // without a primary, the monitor at 0x0
+        app.monitors[1].primary = false;
+        app.handle_key_event(KeyCode::Char('0').into());
+        assert_eq!(app.selected_monitor, 0);
+
+        // then the first enabled one
+        app.monitors[0].enabled = false;
+        app.handle_key_event(KeyCode::Char('0').into());
+        assert_eq!(app.selected_monitor, 1);
+    }
+
+    #[test]
+    fn cycle_scale_key_event() -> io::Result<()> {
+        let mut app = App{
+            monitors: test_monitors(),
+            selected_monitor: 0,
+            ..Default::default()
+        };
+
+        let mut scales = vec![];
+        for _ in 0..6 {
+            app.handle_key_event(KeyCode::Char('z').into());
+            scales.push(app.monitors[0].scale.unwrap());
+        }
+        assert_eq!(scales, vec![1.25, 1.6, 1.75, 2.0, 0.5, 0.6]);
+        assert_eq!(app.mode, TUIMode::View);
+
+        app.monitors[0].locked = true;
+        app.handle_key_event(KeyCode::Char('z').into());
+        assert_eq!(app.monitors[0].scale, Some(0.6));
+
+        Ok(())
+    }
+
+    #[test]
+    fn pin_mode_key_event() -> io::Result<()> {
+        let mut app = App{
+            monitors: test_monitors(),
+            selected_monitor: 0,
+            ..Default::default()
+        };
+
+        // the second row moves up once pinned, and stays highlighted
+        app.handle_key_event(KeyCode::Char('r').into());
+        app.handle_key_event(KeyCode::Char('j').into());
+        let mode = app.monitors[0].modes[app.monitors[0].sorted_mode_indices()[1]].clone();
+        app.handle_key_event(KeyCode::Char('p').into());
+        assert_eq!(app.config.pinned_modes, vec![Resolutions::pin_label(&mode)]);
+        assert_eq!(app.selected_resolution, 0);
+
+        app.handle_key_event(KeyCode::Char(' ').into());
+        assert_eq!(app.monitors[0].get_current_resolution(), Some(&monitor::Resolution { current: true, ..mode }));
+
+        app.handle_key_event(KeyCode::Char('p').into());
+        assert!(app.config.pinned_modes.is_empty());
+        assert_eq!(app.selected_resolution, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn page_through_lists_key_event() -> io::Result<()> {
+        let mut monitors = test_monitors();
+        monitors[0].modes = (0..12)
+            .map(|i| monitor::Resolution { width: 2000 - i, height: 1000, refresh: 60.0, preferred: false, current: false, interlaced: false, custom: false })
+            .collect();
+        let mut app = App{
+            monitors,
+            selected_monitor: 0,
+            ..Default::default()
+        };
+
+        app.handle_key_event(KeyCode::Char('r').into());
+        app.handle_key_event(KeyCode::PageDown.into());
+        assert_eq!(app.selected_resolution, 5);
+        app.handle_key_event(KeyCode::PageDown.into());
+        app.handle_key_event(KeyCode::PageDown.into());
+        assert_eq!(app.selected_resolution, 11);
+        app.handle_key_event(KeyCode::PageUp.into());
+        assert_eq!(app.selected_resolution, 6);
+        app.handle_key_event(KeyCode::Home.into());
+        assert_eq!(app.selected_resolution, 0);
+        app.handle_key_event(KeyCode::PageUp.into());
+        assert_eq!(app.selected_resolution, 0);
+        app.handle_key_event(KeyCode::End.into());
+        assert_eq!(app.selected_resolution, 11);
+        app.handle_key_event(KeyCode::Esc.into());
+
+        app.handle_key_event(KeyCode::Char('s').into());
+        app.handle_key_event(KeyCode::PageDown.into());
+        assert_eq!(app.selected_scale, 5);
+        app.handle_key_event(KeyCode::PageDown.into());
+        assert_eq!(app.selected_scale, 8);
+        app.handle_key_event(KeyCode::Home.into());
+        assert_eq!(app.selected_scale, 0);
+        app.handle_key_event(KeyCode::End.into());
+        assert_eq!(app.selected_scale, utils::ScaleValue::table().len() - 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn handle_mode_view_arrow_key_event() -> io::Result<()> {
+        let mut app = App{
+            monitors: test_monitors(),
+            selected_monitor: 0,
+            ..Default::default()
+        };
+
+        app.handle_key_event(KeyCode::Up.into());
+        assert_eq!(app.selected_monitor, 1);
+
+        app.handle_key_event(KeyCode::Down.into());
+        assert_eq!(app.selected_monitor, 0);
+
+        app.handle_key_event(KeyCode::Down.into());
+        assert_eq!(app.selected_monitor, app.monitors.len() - 1);
+
+        app.handle_key_event(KeyCode::Up.into());
+        assert_eq!(app.selected_monitor, 0);
+        
+        Ok(())
+    }
+
+    #[test]
+    fn handle_mode_move_arrow_key_event() -> io::Result<()> {
+        let mut app = App{
+            monitors: test_monitors(),
+            selected_monitor: 0,
+            ..Default::default()
+        };
+ 
+        app.handle_key_event(KeyCode::Char('m').into());
+        assert_eq!(app.mode, TUIMode::Move);
+
+        // Shift+Up moves -10
+        app.handle_key_event(KeyEvent{
+            code: KeyCode::Up,
+            modifiers: KeyModifiers::SHIFT,
+            kind: KeyEventKind::Press,
+            state: event::KeyEventState::empty(),
+        });
+        let monitor = app.monitors[app.selected_monitor].clone();
+        assert_eq!(monitor.position.unwrap().y, -10);
+
+        // Shift+Down moves +10
+        app.handle_key_event(KeyEvent{
+             code: KeyCode::Down,
+             modifiers: KeyModifiers::SHIFT,
+             kind: KeyEventKind::Press,
+             state: event::KeyEventState::empty(),
+        });
+        let monitor = app.monitors[app.selected_monitor].clone();
+        assert_eq!(monitor.position.unwrap().y, 0);
+
+        // Shift+Left moves -10
+        app.handle_key_event(KeyEvent{
+             code: KeyCode::Left,
+             modifiers: KeyModifiers::SHIFT,
+             kind: KeyEventKind::Press,
+             state: event::KeyEventState::empty(),
+        });
+        let monitor = app.monitors[app.selected_monitor].clone();
+        assert_eq!(monitor.position.unwrap().x, -10);
+
+        // Shift+Right moves +10
+        app.handle_key_event(KeyEvent{
+             code: KeyCode::Right,
+             modifiers: KeyModifiers::SHIFT,
+             kind: KeyEventKind::Press,
+             state: event::KeyEventState::empty(),
+        });
+        let monitor = app.monitors[app.selected_monitor].clone();
+        assert_eq!(monitor.position.unwrap().x, 0);
+
+
+        // Snap Test
+        // Move to 100
+        app.monitors[app.selected_monitor].position.as_mut().unwrap().y = 100;
+        
+        // Up (no shift) should snap to 0 (which is a generic target for all monitors)
+        app.handle_key_event(KeyCode::Up.into());
+        let monitor = app.monitors[app.selected_monitor].clone();
+        assert_eq!(monitor.position.unwrap().y, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn handle_mode_resolution_arrow_key_event() -> io::Result<()> {
+        let mut app = App{
+            monitors: test_monitors(),
+            selected_monitor: 0,
+            ..Default::default()
+        };
+
+        app.handle_key_event(KeyCode::Char('r').into());
+        assert_eq!(app.mode, TUIMode::Resolution);
+
+        app.selected_resolution = 0;
+        app.handle_key_event(KeyCode::Down.into());
+        assert_eq!(app.selected_resolution, 1);
+
+        app.handle_key_event(KeyCode::Up.into());
+        assert_eq!(app.selected_resolution, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn handle_mode_scale_arrow_key_event() -> io::Result<()> {
+        let mut app = App{
+            monitors: test_monitors(),
+            selected_monitor: 0,
+            ..Default::default()
+        };
+
+        app.handle_key_event(KeyCode::Char('s').into());
+        assert_eq!(app.mode, TUIMode::Scale);
+
+        app.selected_scale = 0;
+        app.handle_key_event(KeyCode::Down.into());
+        assert_eq!(app.selected_scale, 1);
+
+        app.handle_key_event(KeyCode::Up.into());
+        assert_eq!(app.selected_scale, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn cycle_layout_key_event() -> io::Result<()> {
+        let mut app = App{
+            monitors: test_monitors(),
+            ..Default::default()
+        };
+
+        app.handle_key_event(KeyCode::Char('v').into());
+        assert_eq!(app.layout, LayoutPreset::MapOnly);
+
+        app.handle_key_event(KeyCode::Char('v').into());
+        assert_eq!(app.layout, LayoutPreset::ListOnly);
+
+        app.handle_key_event(KeyCode::Char('v').into());
+        assert_eq!(app.layout, LayoutPreset::MapAndList);
+
+        Ok(())
+    }
 
     #[test]
-    fn handle_mode_scale_arrow_key_event() -> io::Result<()> {
+    fn render_map_only_layout() {
+        let monitors = test_monitors();
+        let app = App{
+            monitors: monitors.clone(),
+            layout: LayoutPreset::MapOnly,
+            ..Default::default()
+        };
+        let mut buf = Buffer::empty(Rect::new(0, 0, 100, 30));
+        (&app).render(buf.area, &mut buf);
+
+        let map = Map::for_test(&monitors);
+        let mut expected = Buffer::empty(Rect::new(0, 0, 100, 30));
+        map.render(expected.area, &mut expected);
+
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn duplicate_settings_key_event() -> io::Result<()> {
+        let mut monitors = test_monitors();
+        monitors[0].scale = Some(1.5);
+        monitors[0].transform = Some("90".to_string());
+        monitors[0].bit_depth = Some(10);
+
+        let mut compatible = monitors[0].clone();
+        compatible.name = "Monitor 3".to_string();
+        compatible.scale = Some(1.0);
+        compatible.transform = None;
+        compatible.set_current_resolution(1);
+
+        let mut incompatible = monitors[1].clone();
+        incompatible.name = "Monitor 4".to_string();
+        incompatible.modes.remove(0);
+
+        let mut locked = compatible.clone();
+        locked.name = "Monitor 5".to_string();
+        locked.locked = true;
+
+        monitors.push(compatible);
+        monitors.push(incompatible);
+        monitors.push(locked);
+
         let mut app = App{
-            monitors: test_monitors(),
+            monitors,
             selected_monitor: 0,
             ..Default::default()
         };
 
-        app.handle_key_event(KeyCode::Char('s').into());
-        assert_eq!(app.mode, TUIMode::Scale);
+        app.handle_key_event(KeyCode::Char('c').into());
 
-        app.selected_scale = 0;
+        for index in [1, 2] {
+            let monitor = &app.monitors[index];
+            assert_eq!(monitor.scale, Some(1.5));
+            assert_eq!(monitor.transform, Some("90".to_string()));
+            assert_eq!(monitor.bit_depth, Some(10));
+            let mode = monitor.get_current_resolution().unwrap();
+            assert_eq!((mode.width, mode.height), (1920, 1080));
+        }
+
+        let skipped = &app.monitors[3];
+        assert_eq!(skipped.scale, Some(1.25));
+        assert_eq!(skipped.transform, None);
+        assert_eq!(skipped.bit_depth, None);
+        let mode = skipped.get_current_resolution().unwrap();
+        assert_eq!((mode.width, mode.height), (1280, 720));
+
+        let locked = &app.monitors[4];
+        assert_eq!(locked.scale, Some(1.0));
+        assert_eq!(locked.transform, None);
+        let mode = locked.get_current_resolution().unwrap();
+        assert_eq!((mode.width, mode.height), (1280, 720));
+
+        let popup = app.popup.take().expect("No skipped monitors popup");
+        assert_eq!(popup.title, "Copy settings");
+        assert_eq!(popup.lines, vec![
+            "Not copied from Monitor 1:".to_string(),
+            "Monitor 4: no mode matching Monitor 1's".to_string(),
+            "Monitor 5: locked".to_string(),
+        ]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn snap_walks_through_aligned_edges() -> io::Result<()> {
+        let mut monitors = test_monitors();
+        // 1920x1080 at 160% -> 675 logical pixels high, centered at 337.5
+        monitors[1].enabled = true;
+        monitors[1].scale = Some(1.6);
+        monitors[1].set_current_resolution(0);
+
+        let mut app = App{
+            monitors,
+            selected_monitor: 0,
+            mode: TUIMode::Move,
+            ..Default::default()
+        };
+
+        let walk = |app: &mut App, key: char| {
+            app.handle_key_event(KeyCode::Char(key).into());
+            app.monitors[0].position.clone().unwrap().y
+        };
+
+        // down: center on the top edge, top on the center, top on the bottom edge
+        assert_eq!(walk(&mut app, 'j'), 135);
+        assert_eq!(walk(&mut app, 'j'), 337);
+        assert_eq!(walk(&mut app, 'j'), 675);
+        assert_eq!(walk(&mut app, 'j'), 675);
+
+        // up: back through the same edges without 1px steps
+        assert_eq!(walk(&mut app, 'k'), 337);
+        assert_eq!(walk(&mut app, 'k'), 135);
+        assert_eq!(walk(&mut app, 'k'), 0);
+        // and on to the next edge above: both centers aligned
+        assert_eq!(walk(&mut app, 'k'), -203);
+
+        Ok(())
+    }
+
+    #[test]
+    fn snap_nearest_key_event() -> io::Result<()> {
+        let mut monitors = test_monitors();
+        monitors[1].enabled = true;
+        monitors[1].scale = Some(1.0);
+        // 5px right of the first monitor, its bottom 60px above the first one's
+        monitors[1].position = Some(monitor::Position { x: 1925, y: 300 });
+        let mut app = App{
+            monitors,
+            selected_monitor: 1,
+            mode: TUIMode::Move,
+            ..Default::default()
+        };
+
+        app.handle_key_event(KeyCode::Char('a').into());
+        assert_eq!(app.monitors[1].position, Some(monitor::Position { x: 1920, y: 300 }));
+
+        // then the next closest, along y
+        app.handle_key_event(KeyCode::Char('a').into());
+        assert_eq!(app.monitors[1].position, Some(monitor::Position { x: 1920, y: 360 }));
+
+        Ok(())
+    }
+
+    #[test]
+    fn arrows_only_movement_keys() -> io::Result<()> {
+        let mut app = App{
+            monitors: test_monitors(),
+            config: Configuration {
+                movement_keys: utils::MovementKeys::Arrows,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // list navigation
+        app.handle_key_event(KeyCode::Char('j').into());
+        assert_eq!(app.selected_monitor, 0);
         app.handle_key_event(KeyCode::Down.into());
-        assert_eq!(app.selected_scale, 1);
+        assert_eq!(app.selected_monitor, 1);
+        app.handle_key_event(KeyCode::Char('k').into());
+        assert_eq!(app.selected_monitor, 1);
+        app.handle_key_event(KeyCode::Up.into());
+        assert_eq!(app.selected_monitor, 0);
 
+        // moves, snapping to the second monitor's edges
+        app.monitors[1].enable();
+        app.handle_key_event(KeyCode::Char('m').into());
+        let start = app.monitors[0].position.clone();
+        for key in ['h', 'j', 'k', 'l', 'H', 'J', 'K', 'L'] {
+            app.handle_key_event(KeyCode::Char(key).into());
+        }
+        assert_eq!(app.monitors[0].position, start);
+        app.handle_key_event(KeyCode::Right.into());
+        app.handle_key_event(KeyCode::Down.into());
+        assert_ne!(app.monitors[0].position, start);
+        let moved = app.monitors[0].position.clone();
+        app.handle_key_event(KeyCode::Left.into());
         app.handle_key_event(KeyCode::Up.into());
-        assert_eq!(app.selected_scale, 0);
+        assert_ne!(app.monitors[0].position, moved);
+
+        Ok(())
+    }
+
+    #[test]
+    fn exact_snap_keeps_edges_flush() -> io::Result<()> {
+        let mut monitors = test_monitors();
+        // 1280x720 at 125% -> 1024x576
+        monitors[1].enabled = true;
+        let mut app = App{
+            monitors,
+            selected_monitor: 1,
+            mode: TUIMode::Move,
+            ..Default::default()
+        };
+        app.config.exact_snap = true;
+
+        let edges = |monitor: &Monitor, vertical: bool| {
+            let (x, y, w, h) = monitor.get_geometry();
+            if vertical { vec![y, y + h / 2.0, y + h] } else { vec![x, x + w / 2.0, x + w] }
+        };
+        for (i, key) in "hhhhhjjjjlllkkkhhjjl".chars().enumerate() {
+            app.handle_key_event(KeyCode::Char(key).into());
+            let vertical = key == 'j' || key == 'k';
+            let mut targets = edges(&app.monitors[0], vertical);
+            targets.push(0.0);
+            let aligned = edges(&app.monitors[1], vertical)
+                .iter()
+                .any(|edge| targets.contains(edge));
+            assert!(aligned, "snap {} ({}) left {:?}", i, key, app.monitors[1].position);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn exact_snap_fractional_scale() {
+        let snap_up = |exact: bool| {
+            let mut monitors = test_monitors();
+            // 1280x720 at 1.3 -> 553.85 high, center 276.92 below its top
+            monitors[1].enabled = true;
+            monitors[1].scale = Some(1.3);
+            monitors[1].position = Some(monitor::Position { x: 1920, y: 300 });
+            let mut app = App{
+                monitors,
+                selected_monitor: 1,
+                mode: TUIMode::Move,
+                config: Configuration { exact_snap: exact, ..Default::default() },
+                ..Default::default()
+            };
+            // the center snaps onto Monitor 1's center at 540
+            app.handle_key_event(KeyCode::Char('k').into());
+            app.monitors[1].position.clone().unwrap().y
+        };
+
+        // 540 - 276.92 = 263.08: rounded, the center ends above 540
+        assert_eq!(snap_up(false), 263);
+        // exact: it stops at the target, no crossing
+        assert_eq!(snap_up(true), 264);
+    }
+
+    #[test]
+    fn render_stacked_monitors_warning() {
+        let mut monitors = test_monitors();
+        monitors[1].enabled = true;
+        monitors[1].position = monitors[0].position.clone();
+        let app = App{
+            monitors,
+            ..Default::default()
+        };
+        let mut buf = Buffer::empty(Rect::new(0, 0, 110, 30));
+        (&app).render(buf.area, &mut buf);
+
+        let footer = row_text(&buf, 29);
+        assert!(footer.contains("Monitor 1 and Monitor 2 are stacked at (0,0)"));
+    }
+
+    #[test]
+    fn pulse_selection() -> io::Result<()> {
+        let render = |app: &App| {
+            let mut buf = Buffer::empty(Rect::new(0, 0, 100, 30));
+            app.render(buf.area, &mut buf);
+            buf
+        };
+        let mut app = App{
+            monitors: test_monitors(),
+            ..Default::default()
+        };
+        let idle = |app: &mut App| app.handle_pending_events(|_| Ok(false), || Ok(Event::FocusGained));
+
+        // off: the same on every frame
+        let first = render(&app);
+        idle(&mut app)?;
+        assert_eq!(render(&app), first);
+
+        // on: the border alternates
+        app.config.pulse_selection = true;
+        let frames: Vec<Buffer> = (0..3).map(|_| { idle(&mut app).unwrap(); render(&app) }).collect();
+        assert_ne!(frames[0], frames[1]);
+        assert_eq!(frames[0], frames[2]);
+        let border = |buf: &Buffer| (0..buf.area.width).map(|x| buf[(x, 2)].fg).collect::<Vec<_>>();
+        assert!(border(&frames[0]).contains(&Color::LightYellow) != border(&frames[1]).contains(&Color::LightYellow));
+
+        Ok(())
+    }
+
+    #[test]
+    fn render_mixed_scale_note() {
+        let footer = |app: &App| {
+            let mut buf = Buffer::empty(Rect::new(0, 0, 130, 30));
+            app.render(buf.area, &mut buf);
+            row_text(&buf, 29)
+        };
+        let mut monitors = test_monitors();
+        monitors[1].enabled = true;
+        monitors[1].position = Some(monitor::Position { x: 1920, y: 0 });
+        monitors[1].scale = Some(1.0);
+        let mut app = App{
+            monitors,
+            ..Default::default()
+        };
+        assert!(!footer(&app).contains("different scales"));
+
+        app.monitors[1].scale = Some(2.0);
+        assert!(footer(&app).contains("different scales"), "{}", footer(&app));
+
+        app.handle_key_event(KeyCode::Char('X').into());
+        assert!(!footer(&app).contains("different scales"));
+    }
+
+    #[test]
+    fn disable_all_but_selected_key_event() -> io::Result<()> {
+        let mut monitors = test_monitors();
+        monitors[1].enabled = true;
+        monitors.push(Monitor {
+            name: "Monitor 3".to_string(),
+            enabled: true,
+            position: Some(monitor::Position { x: 3200, y: 0 }),
+            scale: Some(2.0),
+            ..monitors[0].clone()
+        });
+        let mut app = App{
+            monitors,
+            selected_monitor: 1,
+            ..Default::default()
+        };
+
+        app.handle_key_event(KeyCode::Char('O').into());
+        let enabled: Vec<bool> = app.monitors.iter().map(|m| m.enabled).collect();
+        assert_eq!(enabled, vec![false, true, false]);
+        assert_eq!(app.monitors[2].saved_position, Some(monitor::Position { x: 3200, y: 0 }));
+        assert_eq!(app.monitors[2].saved_scale, Some(2.0));
+
+        // the map only spans the remaining monitor
+        let canvas = Monitor::get_monitors_canvas(&app.monitors, &Rect::new(0, 0, 100, 30), app.config.canvas_margin);
+        assert_eq!(canvas.x_bounds, [1840.5, 3023.5]);
+
+        app.handle_key_event(KeyCode::Char('E').into());
+        assert!(app.monitors.iter().all(|m| m.enabled));
+        assert_eq!(app.monitors[0].position, Some(monitor::Position { x: 0, y: 0 }));
+        assert_eq!(app.monitors[2].position, Some(monitor::Position { x: 3200, y: 0 }));
+        assert_eq!(app.monitors[2].scale, Some(2.0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn select_sorted_resolution_key_event() -> io::Result<()> {
+        let mut monitors = test_monitors();
+        monitors[0].modes.reverse();
+        let mut app = App{
+            monitors,
+            selected_monitor: 0,
+            ..Default::default()
+        };
+
+        // the first row is the largest mode, stored last
+        app.handle_key_event(KeyCode::Char('r').into());
+        app.handle_key_event(KeyCode::Char(' ').into());
+        assert!(app.monitors[0].modes[1].current);
+        assert_eq!(app.monitors[0].modes[1].width, 1920);
+
+        app.handle_key_event(KeyCode::Char('j').into());
+        app.handle_key_event(KeyCode::Char(' ').into());
+        assert!(app.monitors[0].modes[0].current);
+        assert!(!app.monitors[0].modes[1].current);
+
+        Ok(())
+    }
+
+    #[test]
+    fn load_monitors_selects_preferred_mode() {
+        let stdout = r#"[{
+            "name": "eDP-1",
+            "enabled": true,
+            "modes": [
+                {"width": 1920, "height": 1200, "refresh": 60.0, "preferred": false, "current": false},
+                {"width": 2880, "height": 1800, "refresh": 120.0, "preferred": true, "current": false}
+            ],
+            "position": {"x": 0, "y": 0},
+            "scale": 2.0
+        }]"#;
+        let runner = Runner(Rc::new(FakeRunner::with_output("wlr-randr", stdout)));
+
+        let monitors = App::load_monitors(&runner, &test_config("preferred-mode"));
+
+        assert!(!monitors[0].modes[0].current);
+        assert!(monitors[0].modes[1].current);
+    }
+
+    #[test]
+    fn quit_with_unwritten_changes() {
+        let mut app = App {
+            monitors: test_monitors(),
+            ..Default::default()
+        };
+        app.saved_monitors = Some(app.monitors.clone());
+        app.monitors[0].position = Some(Position { x: 100, y: 0 });
+
+        app.handle_key_event(KeyCode::Char('q').into());
+        assert!(!app.exit);
+        let popup = app.popup.clone().expect("No prompt");
+        assert_eq!(popup.lines, vec!["Quit without writing config? (y/n)".to_string()]);
+
+        // n keeps the app open, q asks again
+        app.handle_key_event(KeyCode::Char('n').into());
+        assert!(!app.exit && app.popup.is_none());
+        app.handle_key_event(KeyCode::Char('q').into());
+        assert!(app.popup.is_some() && app.prompt == Some(Prompt::Quit));
+    }
+
+    #[test]
+    fn monitor_without_position() {
+        let mut monitors = test_monitors();
+        monitors[1].enable();
+        monitors[1].position = None;
+
+        // drawn at 0x0 instead of panicking
+        let app = App { monitors: monitors.clone(), ..Default::default() };
+        let mut buf = Buffer::empty(Rect::new(0, 0, 100, 30));
+        app.render(buf.area, &mut buf);
+        Monitor::get_monitors_canvas(&monitors, &buf.area, app.config.canvas_margin);
+
+        let stdout = r#"[{"name": "HEADLESS-9", "enabled": false, "scale": 1.0, "modes": [
+            {"width": 1920, "height": 1080, "refresh": 60.0, "preferred": true, "current": false}
+        ]}]"#;
+        let runner = Runner(Rc::new(FakeRunner::with_output("wlr-randr", stdout)));
+        let monitors = App::load_monitors(&runner, &test_config("without-position"));
+        assert_eq!(monitors[0].position, Some(Position { x: 0, y: 0 }));
+    }
+
+    #[test]
+    fn load_monitors_default_scale() {
+        let stdout = r#"[
+            {"name": "DP-1", "enabled": true, "modes": [], "position": {"x": 0, "y": 0}, "scale": 1.0},
+            {"name": "DP-2", "enabled": true, "modes": [], "position": {"x": 1920, "y": 0}},
+            {"name": "DP-3", "enabled": true, "modes": [], "position": {"x": 3840, "y": 0}, "scale": 1.5}
+        ]"#;
+        let runner = Runner(Rc::new(FakeRunner::with_output("wlr-randr", stdout)));
+        let config = Configuration {
+            default_scale: Some(1.25),
+            ..test_config("default-scale")
+        };
+        // a saved monitor keeps its stored scale
+        config.save_monitor_state(&[Monitor {
+            name: "DP-1".to_string(),
+            scale: Some(1.0),
+            position: Some(monitor::Position { x: 0, y: 0 }),
+            ..Default::default()
+        }]).expect("Failed to save state");
+
+        let monitors = App::load_monitors(&runner, &config);
+
+        let scales: Vec<Option<f32>> = monitors.iter().map(|m| m.scale).collect();
+        assert_eq!(scales, vec![Some(1.0), Some(1.25), Some(1.5)]);
+    }
+
+    #[test]
+    fn handle_mode_settings_key_event() -> io::Result<()> {
+        let mut app = App{
+            monitors: test_monitors(),
+            selected_monitor: 0,
+            ..Default::default()
+        };
+
+        app.handle_key_event(KeyCode::Char('g').into());
+        assert_eq!(app.mode, TUIMode::Settings);
+
+        // rotation
+        app.handle_key_event(KeyCode::Char(' ').into());
+        assert_eq!(app.monitors[0].transform, Some("90".to_string()));
+
+        // vrr
+        app.handle_key_event(KeyCode::Char('j').into());
+        assert_eq!(app.selected_setting, 1);
+        app.handle_key_event(KeyCode::Enter.into());
+        assert!(app.monitors[0].vrr);
+        app.handle_key_event(KeyCode::Enter.into());
+        assert!(!app.monitors[0].vrr);
+
+        // label, typed keys don't leak to the global bindings
+        app.selected_setting = 5;
+        app.handle_key_event(KeyCode::Enter.into());
+        for c in "qwv".chars() {
+            app.handle_key_event(KeyCode::Char(c).into());
+        }
+        app.handle_key_event(KeyCode::Backspace.into());
+        app.handle_key_event(KeyCode::Enter.into());
+        assert_eq!(app.monitors[0].label, Some("qw".to_string()));
+        assert!(!app.exit);
+
+        app.handle_key_event(KeyCode::Char('k').into());
+        assert_eq!(app.selected_setting, 4);
+
+        app.handle_key_event(KeyCode::Esc.into());
+        assert_eq!(app.mode, TUIMode::View);
+
+        Ok(())
+    }
+
+    #[test]
+    fn handle_mode_move_place_relative_key_event() -> io::Result<()> {
+        let mut monitors = test_monitors();
+        monitors[0].enabled = false;
+        monitors[1].enabled = true;
+        monitors[1].position = Some(monitor::Position { x: 5000, y: 5000 });
+        monitors.push(test_monitors()[0].clone());
+
+        let mut app = App{
+            monitors,
+            selected_monitor: 1,
+            mode: TUIMode::Move,
+            ..Default::default()
+        };
+
+        // the disabled monitor is never a target
+        app.handle_key_event(KeyEvent::new(KeyCode::Right, KeyModifiers::ALT));
+        assert_eq!(app.monitors[1].position, Some(monitor::Position { x: 1920, y: 0 }));
+
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::ALT));
+        assert_eq!(app.monitors[1].position, Some(monitor::Position { x: 0, y: 1080 }));
+
+        Ok(())
+    }
+
+    #[test]
+    fn native_nudge_key_event() {
+        let mut monitors = test_monitors();
+        monitors[1].scale = Some(2.0);
+        let mut app = App{
+            monitors,
+            selected_monitor: 1,
+            mode: TUIMode::Move,
+            config: Configuration {
+                coarse_move_step: 20,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        app.handle_key_event(KeyEvent::new(KeyCode::Right, KeyModifiers::CONTROL));
+        assert_eq!(app.monitors[1].position, Some(monitor::Position { x: 1940, y: 0 }));
+
+        app.handle_key_event(KeyCode::Char('n').into());
+        assert_eq!(app.coordinates, Coordinates::Native);
+        app.handle_key_event(KeyEvent::new(KeyCode::Right, KeyModifiers::CONTROL));
+        assert_eq!(app.monitors[1].position, Some(monitor::Position { x: 1950, y: 0 }));
+        app.handle_key_event(KeyCode::Char('K').into());
+        assert_eq!(app.monitors[1].position, Some(monitor::Position { x: 1950, y: -5 }));
+    }
+
+    #[test]
+    fn validate_key_event() {
+        let mut monitors = test_monitors();
+        monitors[1].enabled = true;
+        monitors[1].position = Some(monitor::Position { x: -10, y: 0 });
+        let mut app = App{
+            monitors,
+            ..Default::default()
+        };
+
+        app.handle_key_event(KeyCode::Char('!').into());
+        assert_eq!(app.popup.as_ref().map(|p| p.lines.len()), Some(2));
+        assert_eq!(app.popup.as_ref().map(|p| p.title.as_str()), Some("Validate"));
+
+        // j scrolls, any other key closes
+        app.handle_key_event(KeyCode::Char('j').into());
+        assert_eq!(app.popup.as_ref().map(|p| p.scroll), Some(1));
+        app.handle_key_event(KeyCode::Char('x').into());
+        assert!(app.popup.is_none());
+
+        app.monitors[1].position = Some(monitor::Position { x: 1920, y: 0 });
+        app.handle_key_event(KeyCode::Char('!').into());
+        assert_eq!(app.popup.take().expect("No report").lines, vec!["No issue found".to_string()]);
+    }
+
+    #[test]
+    fn coarse_move_key_event() {
+        let mut app = App{
+            monitors: test_monitors(),
+            selected_monitor: 1,
+            mode: TUIMode::Move,
+            config: Configuration {
+                coarse_move_step: 100,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        app.handle_key_event(KeyEvent::new(KeyCode::Right, KeyModifiers::CONTROL));
+        assert_eq!(app.monitors[1].position, Some(monitor::Position { x: 2020, y: 0 }));
+
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('k'), KeyModifiers::CONTROL));
+        assert_eq!(app.monitors[1].position, Some(monitor::Position { x: 2020, y: -100 }));
+        assert_eq!(app.mode, TUIMode::Move);
+    }
+
+    #[test]
+    fn write_apply_key_events() -> io::Result<()> {
+        let path = std::env::temp_dir().join("display-tui-apply-test.conf");
+        let runner = Rc::new(FakeRunner::default());
+        let mut app = App{
+            monitors: test_monitors(),
+            config: Configuration {
+                monitors_config_path: path.to_str().unwrap().to_string(),
+                ..Default::default()
+            },
+            runner: Runner(runner.clone()),
+            ..Default::default()
+        };
+        let expected_call = (
+            "hyprctl".to_string(),
+            vec![
+                "--batch".to_string(),
+                "keyword monitor Monitor 1, 1920x1080@60, 0x0, 1, transform,0 ; keyword monitor Monitor 2, disabled".to_string(),
+            ],
+        );
+
+        // write and apply, stay open
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL));
+        assert!(!app.exit);
+        assert_eq!(app.mode, TUIMode::View);
+        assert!(path.exists());
+        assert_eq!(*runner.calls.borrow(), vec![expected_call.clone()]);
 
+        // write, apply and quit
+        std::fs::remove_file(&path)?;
+        app.handle_key_event(KeyCode::Char('Z').into());
+        assert!(app.exit);
+        assert!(path.exists());
+        assert_eq!(*runner.calls.borrow(), vec![expected_call.clone(), expected_call]);
+
+        std::fs::remove_file(&path)?;
         Ok(())
     }
+
+    #[test]
+    fn failing_commands_key_events() {
+        // hyprctl exits with an error: nothing counts as applied or verified
+        let runner = Rc::new(FakeRunner::with_failure("hyprctl", "Couldn't connect to Hyprland"));
+        let mut app = App{
+            monitors: test_monitors(),
+            applied_monitors: Some(test_monitors()),
+            config: test_config("failing-commands"),
+            runner: Runner(runner.clone()),
+            ..Default::default()
+        };
+        app.monitors[0].scale = Some(2.0);
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL));
+        assert!(app.unapplied());
+        let popup = app.popup.take().expect("No apply error");
+        assert_eq!(popup.title, "Apply failed");
+        assert_eq!(popup.lines, vec!["hyprctl: Couldn't connect to Hyprland".to_string()]);
+
+        // Z stays open so the error can be read
+        app.handle_key_event(KeyCode::Char('Z').into());
+        assert!(!app.exit);
+        assert_eq!(app.popup.take().map(|p| p.title), Some("Apply failed".to_string()));
+
+        app.handle_key_event(KeyCode::Char('V').into());
+        let popup = app.popup.take().expect("No verify error");
+        assert_eq!(popup.title, "Verify failed");
+        assert_eq!(popup.lines, vec!["hyprctl: Couldn't connect to Hyprland".to_string()]);
+        assert!(runner.calls.borrow().iter().all(|(program, _)| program == "hyprctl"));
+
+        // wl-copy exits with an error: nothing was copied
+        let mut app = App{
+            monitors: test_monitors(),
+            runner: Runner(Rc::new(FakeRunner::with_failure("wl-copy", "No Wayland display"))),
+            ..Default::default()
+        };
+        app.handle_key_event(KeyCode::Char('y').into());
+        let popup = app.popup.take().expect("No copy popup");
+        assert_eq!(popup.title, "Copy failed");
+        assert_eq!(popup.lines[1], "wl-copy: No Wayland display");
+    }
 }
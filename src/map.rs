@@ -1,8 +1,8 @@
 use crossterm::event::{KeyCode,KeyEvent,KeyModifiers};
 use ratatui::{
     buffer::Buffer,
-    layout::Rect,
-    style::{Stylize,Color,Style},
+    layout::{Margin, Rect},
+    style::{Stylize,Color,Style,Modifier},
     symbols::{
         Marker,
         border,
@@ -10,33 +10,104 @@ use ratatui::{
     text::Line,
     widgets::{
         Block,
+        Paragraph,
         Widget,
+        Wrap,
         canvas::{
+            self,
             Canvas,
+            Context,
+            Points,
             Rectangle,
         }
     },
 };
 use crate::{
     App,
-    configuration::Configuration,
-    monitor::{Monitor, MonitorCanvas},
+    monitor::{Monitor, MonitorCanvas, Relation},
     rotation::Rotation,
-    utils::TUIMode,
+    utils::{ColorSupport, LabelAlign, TUIMode},
 };
 
+// Below this the labels and borders of the monitors run into each other
+const MIN_WIDTH: u16 = 20;
+const MIN_HEIGHT: u16 = 8;
+
 #[derive(Debug)]
 pub struct Map<'a>{
     pub mode: TUIMode,
     pub selected: usize,
     pub monitors:&'a Vec<Monitor>,
+    pub grid: bool,
+    pub viewport: Viewport,
+    pub margin: f64,
+    // scale hovered in Scale mode, drawn on the selected monitor until chosen
+    pub preview_scale: Option<f32>,
+    // brighter border on the selected monitor, alternated by the tick loop
+    pub pulse: bool,
+    pub label_align: LabelAlign,
+    pub colors: ColorSupport,
+    // native size outline behind the monitors that are scaled
+    pub footprint: bool,
+}
+
+// Offset of the map from the fitted canvas, in fractions of its size.
+// Positive values look towards larger Hyprland coordinates.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Viewport {
+    pub pan_x: f64,
+    pub pan_y: f64,
+}
+
+impl Viewport {
+    pub const STEP: f64 = 0.1;
+
+    pub fn pan(&mut self, dx: f64, dy: f64) {
+        self.pan_x += dx * Viewport::STEP;
+        self.pan_y += dy * Viewport::STEP;
+    }
+
+    pub fn apply(&self, monitor_canvas: &mut MonitorCanvas) {
+        let [left, right] = &mut monitor_canvas.x_bounds;
+        let dx = (*right - *left) * self.pan_x;
+        *left += dx;
+        *right += dx;
+        let [bottom, top] = &mut monitor_canvas.y_bounds;
+        let dy = (*top - *bottom) * self.pan_y;
+        *bottom += dy;
+        *top += dy;
+    }
 }
 
 impl<'a> Widget for Map<'a>{
 
     fn render(self, area: Rect, buf: &mut Buffer) {
+        if let Some(scale) = self.preview_scale {
+            let mut monitors = self.monitors.clone();
+            if let Some(monitor) = monitors.get_mut(self.selected) {
+                monitor.scale = Some(scale);
+            }
+            return Map { monitors: &monitors, preview_scale: None, ..self }.render(area, buf);
+        }
+
+        if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+            Paragraph::new("Terminal too small".yellow())
+                .centered()
+                .wrap(Wrap { trim: true })
+                .render(area, buf);
+            return;
+        }
 
-        let monitor_canvas = Monitor::get_monitors_canvas(self.monitors,&area);
+        // the outlines can be larger than the monitors, keep them in view
+        let with_footprints: Vec<Monitor>;
+        let fitted = if self.footprint {
+            with_footprints = self.monitors.iter().cloned().chain(self.monitors.iter().map(Map::native_footprint)).collect();
+            &with_footprints
+        } else {
+            self.monitors
+        };
+        let mut monitor_canvas = Monitor::get_monitors_canvas(fitted,&area,self.margin);
+        self.viewport.apply(&mut monitor_canvas);
 
         let title = Line::from(" Map ".white().bold());
 
@@ -53,22 +124,41 @@ impl<'a> Widget for Map<'a>{
             .x_bounds(monitor_canvas.x_bounds)
             .y_bounds(monitor_canvas.y_bounds)
             .paint(|ctx| {
+                if self.grid {
+                    Map::render_grid(ctx, &monitor_canvas);
+                    ctx.layer();
+                }
+                if self.footprint {
+                    Map::render_footprints(ctx, &monitor_canvas, self.monitors);
+                    ctx.layer();
+                }
                 let mut index = 0;
                 for monitor in self.monitors {
                     if self.selected != index && monitor.enabled {
-                        self.render_enabled_monitor(ctx,&monitor_canvas, monitor, Color::Blue);
+                        self.render_enabled_monitor(ctx,&monitor_canvas, index, monitor, self.unselected_color(), false);
                     }
                     index += 1;
                 }
                 index = 0;
                 for monitor in self.monitors {
                     if self.selected == index && monitor.enabled {
-                            self.render_enabled_monitor(ctx,&monitor_canvas,monitor, Color::Yellow);
+                            self.render_enabled_monitor(ctx,&monitor_canvas, index, monitor, Color::Yellow, true);
                     }
                     index += 1;
                 }
+                if self.mode == TUIMode::Move && self.monitors.get(self.selected).is_some_and(|m| m.enabled) {
+                    ctx.layer();
+                    self.render_guides(ctx, &monitor_canvas);
+                }
             })
             .render(area, buf);
+
+        if !self.monitors.iter().any(|m| m.enabled) {
+            let inner = Rect { y: area.y + area.height / 2, height: 1, ..area.inner(Margin::new(1, 1)) };
+            Paragraph::new("No display enabled, <e> enables the selected one".yellow())
+                .centered()
+                .render(inner, buf);
+        }
     } 
 
     
@@ -76,7 +166,37 @@ impl<'a> Widget for Map<'a>{
 impl<'a> Map<'a> {
    
     pub fn handle_events(app:&mut App, key_event: KeyEvent) {
+        if app.monitors[app.selected_monitor].locked && key_event.code != KeyCode::Esc {
+            return;
+        }
+        // HJKL fast moves go with hjkl
+        if let KeyCode::Char(c) = key_event.code
+            && !app.config.movement_keys.allows(KeyCode::Char(c.to_ascii_lowercase()))
+        {
+            return;
+        }
         let is_shift = key_event.modifiers.contains(KeyModifiers::SHIFT);
+        if key_event.modifiers.contains(KeyModifiers::ALT) {
+            match key_event.code {
+                KeyCode::Char('k') | KeyCode::Up => Map::place_relative(app, Relation::Above),
+                KeyCode::Char('j') | KeyCode::Down => Map::place_relative(app, Relation::Below),
+                KeyCode::Char('h') | KeyCode::Left => Map::place_relative(app, Relation::LeftOf),
+                KeyCode::Char('l') | KeyCode::Right => Map::place_relative(app, Relation::RightOf),
+                _ => {}
+            }
+            return;
+        }
+        if key_event.modifiers.contains(KeyModifiers::CONTROL) {
+            let step = app.config.coarse_move_step;
+            match key_event.code {
+                KeyCode::Char('k') | KeyCode::Up => Map::move_vertical(app, -step),
+                KeyCode::Char('j') | KeyCode::Down => Map::move_vertical(app, step),
+                KeyCode::Char('h') | KeyCode::Left => Map::move_horizontal(app, -step),
+                KeyCode::Char('l') | KeyCode::Right => Map::move_horizontal(app, step),
+                _ => {}
+            }
+            return;
+        }
         match key_event.code {
             KeyCode::Char('k') => Map::snap_vertical(app, -1),
             KeyCode::Char('K') => Map::move_vertical(app, -10),
@@ -94,145 +214,350 @@ impl<'a> Map<'a> {
             KeyCode::Char('L') => Map::move_horizontal(app, 10),
             KeyCode::Right => if is_shift { Map::move_horizontal(app, 10) } else { Map::snap_horizontal(app, 1) },
             
+            KeyCode::Char('a') => Map::snap_nearest(app),
+
+            KeyCode::Enter if app.config.live_move => Map::confirm_live_move(app),
+            KeyCode::Esc if app.config.live_move => Map::revert_live_move(app),
             KeyCode::Esc => Map::change_mode(app,TUIMode::View),
             _ => {}
         }
     }
+
+    // Keeps the live moved layout, applying a move still held back by the throttle.
+    fn confirm_live_move(app:&mut App) {
+        app.move_snapshot = None;
+        if app.unapplied() {
+            app.apply();
+        }
+        Map::change_mode(app,TUIMode::View);
+    }
+
+    // Puts the monitors back where they were when Move mode was entered.
+    fn revert_live_move(app:&mut App) {
+        if let Some(snapshot) = app.move_snapshot.take() {
+            app.monitors = snapshot;
+            app.apply();
+        }
+        Map::change_mode(app,TUIMode::View);
+    }
     fn change_mode(app:&mut App,mode: TUIMode) {
         // Save monitor state when exiting Move mode
         if app.mode == TUIMode::Move {
-            let _ = Configuration::save_monitor_state(&app.monitors);
+            let _ = app.config.save_monitor_state(&app.monitors);
         }
         app.mode = mode;
     }
+    fn place_relative(app:&mut App, relation: Relation) {
+        if let Some(target) = Monitor::nearest(&app.monitors, app.selected_monitor) {
+            let target = app.monitors[target].clone();
+            app.monitors[app.selected_monitor].place_relative(&target, relation);
+        }
+    }
+
+    // Nudges are in the pixels the list shows, see Coordinates::nudge
     fn move_vertical(app:&mut App, direction: i32) {
-        app.monitors[app.selected_monitor].move_vertical(direction);
+        let monitor = &mut app.monitors[app.selected_monitor];
+        monitor.move_vertical(app.coordinates.nudge(direction, monitor.scale.unwrap_or(1.0)));
     }
-    fn snap_vertical(app:&mut App, direction: i32) {
-        let selected_index = app.selected_monitor;
+    // Edges and center of the selected monitor, and those of the other enabled
+    // monitors plus the origin, along y when `vertical`, along x otherwise.
+    fn snap_lines(monitors: &[Monitor], selected_index: usize, vertical: bool) -> (Vec<f64>, Vec<f64>) {
+        let span = |monitor: &Monitor| {
+            let (x, y, w, h) = monitor.get_geometry();
+            if vertical { (y, h) } else { (x, w) }
+        };
         let mut targets = vec![0.0];
         
-        for (i, monitor) in app.monitors.iter().enumerate() {
+        for (i, monitor) in monitors.iter().enumerate() {
             if i == selected_index || !monitor.enabled { continue; }
-            let (_, y, _, h) = monitor.get_geometry();
-            targets.push(y);
-            targets.push(y + h);
-            targets.push(y + h / 2.0);
+            let (start, size) = span(monitor);
+            targets.push(start);
+            targets.push(start + size);
+            targets.push(start + size / 2.0);
         }
 
-        let (_, sy, _, sh) = app.monitors[selected_index].get_geometry();
-        let sources = vec![sy, sy + sh, sy + sh / 2.0];
-
-        let mut best_delta: Option<f64> = None;
-
-        for s in &sources {
-            for t in &targets {
-                let diff = t - s;
-                if (direction < 0 && diff < -0.1) || (direction > 0 && diff > 0.1) {
-                     match best_delta {
-                         None => best_delta = Some(diff),
-                         Some(current) => {
-                             if diff.abs() < current.abs() {
-                                 best_delta = Some(diff);
-                             }
-                         }
-                     }
-                }
-            }
-        }
-        
-        if let Some(delta) = best_delta {
-            app.monitors[selected_index].move_vertical(delta.round() as i32);
+        let (start, size) = span(&monitors[selected_index]);
+        (vec![start, start + size, start + size / 2.0], targets)
+    }
+
+    // Target line closest to one of the selected monitor's lines, if within `max_distance`.
+    fn guide(monitors: &[Monitor], selected_index: usize, vertical: bool, max_distance: f64) -> Option<f64> {
+        let (sources, targets) = Map::snap_lines(monitors, selected_index, vertical);
+        sources
+            .iter()
+            .flat_map(|s| targets.iter().map(move |t| (t, (t - s).abs())))
+            .filter(|(_, distance)| *distance <= max_distance)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(t, _)| *t)
+    }
+
+    fn snap_vertical(app:&mut App, direction: i32) {
+        let selected_index = app.selected_monitor;
+        let (sources, targets) = Map::snap_lines(&app.monitors, selected_index, true);
+        let (_, sy, _, _) = app.monitors[selected_index].get_geometry();
+
+        if let Some((source, target)) = Map::snap_edges(&sources, targets, direction) {
+            let delta = Map::snap_move(sy, source, target, app.config.exact_snap);
+            app.monitors[selected_index].move_vertical(delta);
         }
     }
 
     fn move_horizontal(app:&mut App, direction: i32) {
-        app.monitors[app.selected_monitor].move_horizontal(direction);
+        let monitor = &mut app.monitors[app.selected_monitor];
+        monitor.move_horizontal(app.coordinates.nudge(direction, monitor.scale.unwrap_or(1.0)));
     }
     fn snap_horizontal(app:&mut App, direction: i32) {
         let selected_index = app.selected_monitor;
-        let mut targets = vec![0.0];
-        
-        for (i, monitor) in app.monitors.iter().enumerate() {
-            if i == selected_index || !monitor.enabled { continue; }
-            let (x, _, w, _) = monitor.get_geometry();
-            targets.push(x);
-            targets.push(x + w);
-            targets.push(x + w / 2.0);
+        let (sources, targets) = Map::snap_lines(&app.monitors, selected_index, false);
+        let (sx, _, _, _) = app.monitors[selected_index].get_geometry();
+
+        if let Some((source, target)) = Map::snap_edges(&sources, targets, direction) {
+            let delta = Map::snap_move(sx, source, target, app.config.exact_snap);
+            app.monitors[selected_index].move_horizontal(delta);
         }
+    }
 
-        let (sx, _, sw, _) = app.monitors[selected_index].get_geometry();
-        let sources = vec![sx, sx + sw, sx + sw / 2.0];
-
-        let mut best_delta: Option<f64> = None;
-
-        for s in &sources {
-            for t in &targets {
-                let diff = t - s;
-                if (direction < 0 && diff < -0.1) || (direction > 0 && diff > 0.1) {
-                     match best_delta {
-                         None => best_delta = Some(diff),
-                         Some(current) => {
-                             if diff.abs() < current.abs() {
-                                 best_delta = Some(diff);
-                             }
-                         }
-                     }
-                }
+    // Smallest snap in any of the four directions, along a single axis.
+    fn snap_nearest(app:&mut App) {
+        let selected_index = app.selected_monitor;
+        let (sx, sy, _, _) = app.monitors[selected_index].get_geometry();
+        let candidates = [(false, sx), (true, sy)].into_iter().flat_map(|(vertical, origin)| {
+            let (sources, targets) = Map::snap_lines(&app.monitors, selected_index, vertical);
+            [-1, 1].into_iter()
+                .filter_map(move |direction| Map::snap_edges(&sources, targets.clone(), direction))
+                .map(move |(source, target)| (vertical, origin, source, target))
+        });
+        let nearest = candidates
+            .min_by(|(_, _, s1, t1), (_, _, s2, t2)| (t1 - s1).abs().total_cmp(&(t2 - s2).abs()));
+
+        if let Some((vertical, origin, source, target)) = nearest {
+            let delta = Map::snap_move(origin, source, target, app.config.exact_snap);
+            let monitor = &mut app.monitors[selected_index];
+            if vertical {
+                monitor.move_vertical(delta);
+            } else {
+                monitor.move_horizontal(delta);
             }
         }
-        
-        if let Some(delta) = best_delta {
-            app.monitors[selected_index].move_horizontal(delta.round() as i32);
+    }
+
+    // Closest (source, target) pair in `direction`: the smallest move that brings
+    // one of the sources onto a target.
+    // Positions are integers, so edges within half a pixel already count as aligned:
+    // this keeps fractional edges (odd scales) from producing 0 or 1 pixel moves.
+    fn snap_edges(sources: &[f64], mut targets: Vec<f64>, direction: i32) -> Option<(f64, f64)> {
+        const ALIGNED: f64 = 0.5;
+        targets.sort_by(|a, b| a.total_cmp(b));
+        targets.dedup_by(|a, b| (*a - *b).abs() <= ALIGNED);
+
+        sources
+            .iter()
+            .flat_map(|s| targets.iter().map(move |t| (*s, *t)))
+            .filter(|(s, t)| (direction < 0 && t - s < -ALIGNED) || (direction > 0 && t - s > ALIGNED))
+            .min_by(|(s1, t1), (s2, t2)| (t1 - s1).abs().total_cmp(&(t2 - s2).abs()))
+    }
+
+    // Integer move of the monitor at `origin` putting `source` on `target`.
+    // With `exact`, the new position is derived from the target edge itself,
+    // rounded so that a fractional edge (odd scales) stops short of the target
+    // instead of crossing it by a sub-pixel: no overlap builds up.
+    fn snap_move(origin: f64, source: f64, target: f64, exact: bool) -> i32 {
+        if !exact {
+            return (target - source).round() as i32;
+        }
+        let position = target - (source - origin);
+        let position = if target > source { position.floor() } else { position.ceil() };
+        (position - origin) as i32
+    }
+
+    pub fn monitor_label(monitor: &Monitor, max_chars: usize) -> String {
+        let mut mode = monitor.get_current_resolution();
+        if mode.is_none() {
+            mode = monitor.get_prefered_resolution();
+        }
+        let label = match mode {
+            Some(res) => format!("{} @{}Hz", monitor.display_name(), res.refresh),
+            None => monitor.display_name().to_string(),
+        };
+        label.chars().take(max_chars).collect()
+    }
+
+    // Smallest round step giving at most a handful of grid lines over `span`.
+    fn grid_step(span: f64) -> f64 {
+        [100.0, 250.0, 500.0, 1000.0, 2000.0, 5000.0]
+            .into_iter()
+            .find(|step| span / step <= 6.0)
+            .unwrap_or(10000.0)
+    }
+
+    // Faint dotted lines at round layout coordinates, labeled with their value.
+    fn render_grid(ctx: &mut Context, monitor_canvas: &MonitorCanvas) {
+        let [left, right] = monitor_canvas.x_bounds;
+        let [bottom, top] = monitor_canvas.y_bounds;
+        let color = Color::DarkGray;
+        // one dot every other cell
+        let dot_x = 2.0 * monitor_canvas.cell_width;
+        let dot_y = 2.0 * monitor_canvas.cell_height;
+        let dots = |from: f64, to: f64, spacing: f64| {
+            let count = ((to - from) / spacing).floor().max(0.0) as usize;
+            (0..=count).map(move |i| from + i as f64 * spacing)
+        };
+
+        let step = Map::grid_step(right - left);
+        let mut x = (left / step).ceil() * step;
+        while x < right {
+            let coords: Vec<(f64, f64)> = dots(bottom, top, dot_y).map(|y| (x, y)).collect();
+            ctx.draw(&Points { coords: &coords, color });
+            ctx.print(x, top, Line::styled(format!("{}", x as i32), color));
+            x += step;
+        }
+
+        // same mirroring as the monitors, labels are Hyprland y values
+        let step = Map::grid_step(top - bottom);
+        let mut y = (bottom / step).ceil() * step;
+        while y < top {
+            let canvas_y = bottom + top - y;
+            let coords: Vec<(f64, f64)> = dots(left, right, dot_x).map(|x| (x, canvas_y)).collect();
+            ctx.draw(&Points { coords: &coords, color });
+            ctx.print(left, canvas_y, Line::styled(format!("{}", y as i32), color));
+            y += step;
+        }
+    }
+
+    // Lines the selected monitor is about to snap to, within two cells.
+    fn render_guides(&self, ctx: &mut Context, monitor_canvas: &MonitorCanvas) {
+        let [left, right] = monitor_canvas.x_bounds;
+        let [bottom, top] = monitor_canvas.y_bounds;
+        let color = Color::Magenta;
+
+        if let Some(x) = Map::guide(self.monitors, self.selected, false, 2.0 * monitor_canvas.cell_width) {
+            ctx.draw(&canvas::Line { x1: x, y1: bottom, x2: x, y2: top, color });
+        }
+        if let Some(y) = Map::guide(self.monitors, self.selected, true, 2.0 * monitor_canvas.cell_height) {
+            // Hyprland y grows downwards, see render_enabled_monitor
+            let y = bottom + top - y;
+            ctx.draw(&canvas::Line { x1: left, y1: y, x2: right, y2: y, color });
+        }
+    }
+
+    // The monitor at scale 1: what its mode covers before the scale divides it.
+    fn native_footprint(monitor: &Monitor) -> Monitor {
+        Monitor { scale: Some(1.0), ..monitor.clone() }
+    }
+
+    fn render_footprints(ctx: &mut Context, monitor_canvas: &MonitorCanvas, monitors: &[Monitor]) {
+        let [bottom, top] = monitor_canvas.y_bounds;
+        for monitor in monitors.iter().filter(|m| m.enabled && m.scale.unwrap_or(1.0) != 1.0) {
+            let (x, y, width, height) = Map::native_footprint(monitor).get_geometry();
+            ctx.draw(&Rectangle {
+                x,
+                y: bottom + top - y - height,
+                width,
+                height,
+                color: Color::Gray,
+            });
+        }
+    }
+
+    // Blue can be hard to tell from yellow on basic terminals, the
+    // selection is reversed there instead.
+    fn unselected_color(&self) -> Color {
+        match self.colors {
+            ColorSupport::Full => Color::Blue,
+            ColorSupport::Basic => Color::White,
         }
     }
 
     pub fn render_enabled_monitor(
         &self,
-        ctx: &mut ratatui::widgets::canvas::Context,
+        ctx: &mut Context,
         monitor_canvas: &MonitorCanvas,
+        index: usize,
         monitor: &Monitor,
         color: Color,
+        selected: bool,
     ) {
         let mut mode = monitor.get_current_resolution();
         if mode.is_none() {
             mode = monitor.get_prefered_resolution();
         }
+        // no usable mode, see get_monitors_canvas
+        let Some(mode) = mode else { return };
 
         let rotation = Rotation::from_transform(&monitor.transform);
-        let (width, height) = if rotation == Rotation::Deg90 || rotation == Rotation::Deg270 {
+        let (width, height) = if rotation.is_portrait() {
             (
-                mode.unwrap().height as f64 / monitor.scale.unwrap() as f64,
-                mode.unwrap().width as f64 / monitor.scale.unwrap() as f64,
+                mode.height as f64 / monitor.scale.unwrap() as f64,
+                mode.width as f64 / monitor.scale.unwrap() as f64,
             )
         } else {
             (
-                mode.unwrap().width as f64 / monitor.scale.unwrap() as f64,
-                mode.unwrap().height as f64 / monitor.scale.unwrap() as f64,
+                mode.width as f64 / monitor.scale.unwrap() as f64,
+                mode.height as f64 / monitor.scale.unwrap() as f64,
             )
         };
-        let x = monitor.position.clone().unwrap().x as f64;
-        let y = (monitor_canvas.top - monitor_canvas.offset_y - monitor.position.clone().unwrap().y) as f64 - height ; 
+        let position = monitor.position.clone().unwrap_or_default();
+        let x = position.x as f64;
+        // Hyprland y grows downwards, canvas y upwards: mirror inside the bounds.
+        // Stay in f64 so monitors sharing an edge land on the same cell.
+        let [bottom, top] = monitor_canvas.y_bounds;
+        let y = bottom + top - position.y as f64 - height;
 
         let x_margin = width * 0.07; 
-        let y_margin = height * 0.07;
-
-        ctx.print(
-            x + x_margin, 
-            y + height - y_margin, 
-            Line::styled(
-                monitor.name.to_string(),
-                color
-            )
-        );
+        // keep the label clear of the double border
+        let y_margin = (height * 0.07).max(2.0 * monitor_canvas.cell_height);
+
+        let max_chars = ((width - 2.0 * x_margin) / monitor_canvas.cell_width).floor().max(0.0) as usize;
+        let label = Map::monitor_label(monitor, max_chars);
+
+        let (label_x, label_y) = match self.label_align {
+            LabelAlign::TopLeft => (x + x_margin, y + height - y_margin),
+            LabelAlign::Center => {
+                let label_width = label.chars().count() as f64 * monitor_canvas.cell_width;
+                (x + (width - label_width) / 2.0, y + height / 2.0)
+            },
+        };
+        let mut style = Style::new().fg(color);
+        if selected && self.colors == ColorSupport::Basic {
+            style = style.add_modifier(Modifier::REVERSED | Modifier::BOLD);
+        }
+        ctx.print(label_x, label_y, Line::styled(label, style));
+
+        // list number in the bottom right corner, left out when it would
+        // share the label's row
+        let number = (index + 1).to_string();
+        if height - 2.0 * y_margin >= monitor_canvas.cell_height {
+            ctx.print(
+                x + width - x_margin - number.len() as f64 * monitor_canvas.cell_width,
+                y + y_margin,
+                Line::styled(number, style)
+            );
+        }
 
+        let pulse_color = match self.colors {
+            ColorSupport::Full => Color::LightYellow,
+            ColorSupport::Basic => Color::White,
+        };
+        let border_color = if selected && self.pulse { pulse_color } else { color };
         ctx.draw(&Rectangle {
             x,
             y,
             width,
             height,
-            color,
+            color: border_color,
         });
+
+        // double border, so the selection doesn't rely on color alone
+        let (inset_x, inset_y) = (monitor_canvas.cell_width, monitor_canvas.cell_height);
+        if selected && width > 2.0 * inset_x && height > 2.0 * inset_y {
+            ctx.draw(&Rectangle {
+                x: x + inset_x,
+                y: y + inset_y,
+                width: width - 2.0 * inset_x,
+                height: height - 2.0 * inset_y,
+                color: border_color,
+            });
+        }
     }
 }
 
@@ -240,50 +565,47 @@ impl<'a> Map<'a> {
 mod tests {
     use super::*;
     use ratatui::style::Style;
-    use crate::test_utils::tests::test_monitors;
+    use crate::test_utils::tests::{row_text, test_monitors};
 
     #[test]
     fn render_map() {
-        let map = Map {
-            selected: 0,
-            mode: TUIMode::View,
-            monitors: &test_monitors(),
-        }; 
+        let monitors = test_monitors();
+        let map = Map::for_test(&monitors);
         let mut buf = Buffer::empty(Rect::new(0, 0, 100, 30));
         
         map.render(buf.area, &mut buf);
 
         let mut expected = Buffer::with_lines(vec![
-            "┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━ Map ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓",
-            "┃                                                                                                  ┃",
-            "┃  █▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀█   ┃",
-            "┃  █     Monitor 1                                                                             █   ┃",
-            "┃  █                                                                                           █   ┃",
-            "┃  █                                                                                           █   ┃",
-            "┃  █                                                                                           █   ┃",
-            "┃  █                                                                                           █   ┃",
-            "┃  █                                                                                           █   ┃",
-            "┃  █                                                                                           █   ┃",
-            "┃  █                                                                                           █   ┃",
-            "┃  █                                                                                           █   ┃",
-            "┃  █                                                                                           █   ┃",
-            "┃  █                                                                                           █   ┃",
-            "┃  █                                                                                           █   ┃",
-            "┃  █                                                                                           █   ┃",
-            "┃  █                                                                                           █   ┃",
-            "┃  █                                                                                           █   ┃",
-            "┃  █                                                                                           █   ┃",
-            "┃  █                                                                                           █   ┃",
-            "┃  █                                                                                           █   ┃",
-            "┃  █                                                                                           █   ┃",
-            "┃  █                                                                                           █   ┃",
-            "┃  █                                                                                           █   ┃",
-            "┃  █                                                                                           █   ┃",
-            "┃  █                                                                                           █   ┃",
-            "┃  █                                                                                           █   ┃",
-            "┃  ▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀   ┃",
-            "┃                                                                                                  ┃",
-            "┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛",
+        "┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━ Map ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓",
+        "┃                                                                                                  ┃",
+        "┃   █▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀█    ┃",
+        "┃   ██▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀██    ┃",
+        "┃   ██    Monitor 1 @60Hz                                                                    ██    ┃",
+        "┃   ██                                                                                       ██    ┃",
+        "┃   ██                                                                                       ██    ┃",
+        "┃   ██                                                                                       ██    ┃",
+        "┃   ██                                                                                       ██    ┃",
+        "┃   ██                                                                                       ██    ┃",
+        "┃   ██                                                                                       ██    ┃",
+        "┃   ██                                                                                       ██    ┃",
+        "┃   ██                                                                                       ██    ┃",
+        "┃   ██                                                                                       ██    ┃",
+        "┃   ██                                                                                       ██    ┃",
+        "┃   ██                                                                                       ██    ┃",
+        "┃   ██                                                                                       ██    ┃",
+        "┃   ██                                                                                       ██    ┃",
+        "┃   ██                                                                                       ██    ┃",
+        "┃   ██                                                                                       ██    ┃",
+        "┃   ██                                                                                       ██    ┃",
+        "┃   ██                                                                                       ██    ┃",
+        "┃   ██                                                                                       ██    ┃",
+        "┃   ██                                                                                       ██    ┃",
+        "┃   ██                                                                                 1     ██    ┃",
+        "┃   ██                                                                                       ██    ┃",
+        "┃   █▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀█    ┃",
+        "┃   ▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀▀    ┃",
+        "┃                                                                                                  ┃",
+        "┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛",
         ]);
         let vertical_line_style = Style::new().fg(Color::Yellow).bg(Color::Yellow);
         
@@ -304,20 +626,369 @@ mod tests {
 
         // Monitor styles
         // Top line y=2
-        expected.set_style(Rect::new(3, 2, 1, 1), vertical_line_style);
-        expected.set_style(Rect::new(4, 2, 91, 1), horizontal_line_style);
-        expected.set_style(Rect::new(95, 2, 1, 1), vertical_line_style);
+        expected.set_style(Rect::new(4, 2, 1, 1), vertical_line_style);
+        expected.set_style(Rect::new(5, 2, 89, 1), horizontal_line_style);
+        expected.set_style(Rect::new(94, 2, 1, 1), vertical_line_style);
 
         // Sides y=3..26
-        expected.set_style(Rect::new(3, 3, 1, 24), vertical_line_style);
-        expected.set_style(Rect::new(95, 3, 1, 24), vertical_line_style);
+        expected.set_style(Rect::new(4, 3, 1, 24), vertical_line_style);
+        expected.set_style(Rect::new(94, 3, 1, 24), vertical_line_style);
         
         // Bottom line y=27
-        expected.set_style(Rect::new(3, 27, 93, 1), horizontal_line_style);
+        expected.set_style(Rect::new(4, 27, 91, 1), horizontal_line_style);
+
+        // Selection double border: top line y=3, sides y=3..25, bottom line y=26
+        expected.set_style(Rect::new(6, 3, 87, 1), horizontal_line_style);
+        expected.set_style(Rect::new(5, 3, 1, 23), vertical_line_style);
+        expected.set_style(Rect::new(93, 3, 1, 23), vertical_line_style);
+        expected.set_style(Rect::new(5, 26, 89, 1), horizontal_line_style);
+
+        // Text y=4
+        expected.set_style(Rect::new(10, 4, 15, 1), horizontal_line_style);
+
+        // Index y=24, in the bottom right corner
+        expected.set_style(Rect::new(87, 24, 1, 1), horizontal_line_style);
+
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn render_map_refresh_label() {
+        let mut monitors = test_monitors();
+        monitors[0].modes[0].refresh = 144.0;
+        let map = Map::for_test(&monitors);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 100, 30));
+
+        map.render(buf.area, &mut buf);
+
+        let label_line = row_text(&buf, 4);
+        assert!(label_line.contains("Monitor 1 @144Hz"));
+    }
+
+    #[test]
+    fn render_map_zero_refresh_only_output() {
+        // a virtual output whose 0Hz modes were all dropped
+        let mut monitors = test_monitors();
+        monitors[1].enabled = true;
+        monitors[1].modes.clear();
+        let map = Map::for_test(&monitors);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 100, 30));
+
+        map.render(buf.area, &mut buf);
+
+        let text: String = (0..buf.area.height).map(|y| row_text(&buf, y)).collect();
+        assert!(text.contains("Monitor 1"), "{}", text);
+        assert!(!text.contains("Monitor 2"), "{}", text);
+    }
 
-        // Text y=3
-        expected.set_style(Rect::new(9, 3, 9, 1), horizontal_line_style);
+    #[test]
+    fn render_map_too_small() {
+        let monitors = test_monitors();
+        let map = Map::for_test(&monitors);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 10, 5));
+
+        map.render(buf.area, &mut buf);
 
+        let mut expected = Buffer::with_lines(vec![
+            " Terminal ",
+            " too small",
+            "          ",
+            "          ",
+            "          ",
+        ]);
+        expected.set_style(Rect::new(1, 0, 8, 1), Style::new().yellow());
+        expected.set_style(Rect::new(1, 1, 9, 1), Style::new().yellow());
         assert_eq!(buf, expected);
     }
+
+    #[test]
+    fn render_map_scale_preview() {
+        let render = |scale: f32, preview_scale: Option<f32>| {
+            let mut monitors = test_monitors();
+            monitors[1].enabled = true;
+            monitors[0].scale = Some(scale);
+            let map = Map {
+                mode: TUIMode::Scale,
+                preview_scale,
+                ..Map::for_test(&monitors)
+            };
+            let mut buf = Buffer::empty(Rect::new(0, 0, 60, 20));
+            map.render(buf.area, &mut buf);
+            buf
+        };
+
+        // the previewed scale draws the same map as the committed one
+        assert_eq!(render(1.0, Some(2.0)), render(2.0, None));
+        assert_ne!(render(1.0, Some(2.0)), render(1.0, None));
+    }
+
+    #[test]
+    fn render_map_monitor_index() {
+        let mut monitors = test_monitors();
+        monitors[1].enabled = true;
+        let map = Map::for_test(&monitors);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 100, 30));
+        map.render(buf.area, &mut buf);
+
+        // below the "Map" title
+        let label = buf.area.positions().find(|&p| p.y > 0 && buf[p].symbol() == "M").expect("No label");
+        // the labels hold digits too, the numbers are on lower rows
+        let find = |symbol: &str| {
+            buf.area.positions()
+                .find(|&p| p.y > label.y && buf[p].symbol() == symbol)
+                .expect("No index")
+        };
+        let (first, second) = (find("1"), find("2"));
+        // bottom right of the first monitor, the second one's further right
+        assert!(first.x > label.x + 15, "{:?} {:?}", first, label);
+        assert!(second.x > first.x, "{:?} {:?}", second, first);
+    }
+
+    #[test]
+    fn render_map_centered_label() {
+        let render = |label_align| {
+            let monitors = test_monitors();
+            let map = Map {
+                selected: 1,
+                label_align,
+                ..Map::for_test(&monitors)
+            };
+            let mut buf = Buffer::empty(Rect::new(0, 0, 100, 30));
+            map.render(buf.area, &mut buf);
+            let row = (1..buf.area.height)
+                .find(|&y| (0..buf.area.width).map(|x| buf[(x, y)].symbol()).collect::<String>().contains("Monitor 1 @60Hz"))
+                .expect("No label");
+            let x = (0..buf.area.width).find(|&x| buf[(x, row)].symbol() == "M").unwrap();
+            (x, row)
+        };
+
+        // the single 1920x1080 monitor fills the map between its margins,
+        // the 15 characters long label is centered in both directions
+        let (x, y) = render(LabelAlign::Center);
+        assert!((41..=44).contains(&x), "{}", x);
+        assert!((13..=16).contains(&y), "{}", y);
+
+        let (x, y) = render(LabelAlign::TopLeft);
+        assert!(x < 15 && y < 6, "{} {}", x, y);
+    }
+
+    #[test]
+    fn render_map_wide_layout_letterboxed() {
+        let monitors: Vec<Monitor> = (0..3)
+            .map(|i| Monitor {
+                name: format!("DP-{}", i + 1),
+                position: Some(crate::monitor::Position { x: i * 1920, y: 0 }),
+                ..test_monitors()[0].clone()
+            })
+            .collect();
+        let map = Map::for_test(&monitors);
+        let area = Rect::new(0, 0, 100, 30);
+        let mut buf = Buffer::empty(area);
+        map.render(area, &mut buf);
+
+        let drawn: Vec<u16> = (1..area.height - 1)
+            .filter(|&y| (1..area.width - 1).any(|x| buf[(x, y)].symbol() != " "))
+            .collect();
+        // 5760x1080 is 16:3, drawn over about 9 of the 28 rows, centered,
+        // instead of the full height
+        let (first, last) = (drawn[0], *drawn.last().unwrap());
+        assert!(first > 8 && last < area.height - 9, "{:?}", drawn);
+        assert!(first.abs_diff(area.height - 1 - last) <= 1, "{:?}", drawn);
+
+        let canvas = Monitor::get_monitors_canvas(&monitors, &area, 50.0);
+        // true proportions: a cell covers twice as much height as width
+        assert!((canvas.cell_height / canvas.cell_width - 2.0).abs() < 1e-9, "{:?}", canvas);
+    }
+
+    #[test]
+    fn render_map_all_disabled() {
+        let mut monitors = test_monitors();
+        monitors[0].disable();
+        let area = Rect::new(0, 0, 60, 10);
+
+        for margin in [50.0, 0.0] {
+            let canvas = Monitor::get_monitors_canvas(&monitors, &area, margin);
+            let [left, right] = canvas.x_bounds;
+            let [bottom, top] = canvas.y_bounds;
+            assert!(left < 0.0 && right > 0.0 && bottom < 0.0 && top > 0.0, "{:?}", canvas);
+            assert!(canvas.cell_width.is_finite() && canvas.cell_width > 0.0, "{:?}", canvas);
+            assert!(canvas.cell_height.is_finite() && canvas.cell_height > 0.0, "{:?}", canvas);
+        }
+
+        let map = Map {
+            mode: TUIMode::Move,
+            grid: true,
+            ..Map::for_test(&monitors)
+        };
+        let mut buf = Buffer::empty(area);
+        map.render(area, &mut buf);
+
+        let row = |y: u16| (0..area.width).map(|x| buf[(x, y)].symbol()).collect::<String>();
+        assert!(row(0).contains(" Map "), "{}", row(0));
+        assert!(row(5).contains("No display enabled"), "{}", row(5));
+    }
+
+    #[test]
+    fn monitor_label_is_clipped() {
+        let monitors = test_monitors();
+        assert_eq!(Map::monitor_label(&monitors[0], 100), "Monitor 1 @60Hz");
+        assert_eq!(Map::monitor_label(&monitors[0], 7), "Monitor");
+        assert_eq!(Map::monitor_label(&monitors[0], 0), "");
+    }
+
+    #[test]
+    fn render_map_side_by_side_flush() {
+        let render = |y: i32| {
+            let mut monitors = test_monitors();
+            monitors[1].enabled = true;
+            monitors[0].position = Some(crate::monitor::Position { x: 0, y });
+            monitors[1].position = Some(crate::monitor::Position { x: 1920, y });
+            let map = Map::for_test(&monitors);
+            let mut buf = Buffer::empty(Rect::new(0, 0, 60, 20));
+            map.render(buf.area, &mut buf);
+            buf
+        };
+
+        let buf = render(0);
+        // left edge, shared edge, right edge plus the selected monitor's inner
+        // border: no gap column between the monitors
+        let row = row_text(&buf, 7);
+        assert_eq!(row.matches('█').count(), 5, "{}", row);
+
+        // the drawing doesn't depend on where the layout sits
+        assert_eq!(render(500), buf);
+        assert_eq!(render(-500), buf);
+    }
+
+    #[test]
+    fn render_map_selection_has_double_border() {
+        let monitors = test_monitors();
+        let render = |selected: usize| {
+            let map = Map {
+                selected,
+                ..Map::for_test(&monitors)
+            };
+            let mut buf = Buffer::empty(Rect::new(0, 0, 100, 30));
+            map.render(buf.area, &mut buf);
+            row_text(&buf, 10)
+        };
+
+        // the selection is visible without relying on color
+        assert_eq!(render(0).matches('█').count(), 4);
+        assert_eq!(render(1).matches('█').count(), 2);
+    }
+
+    #[test]
+    fn render_map_basic_colors() {
+        assert_eq!(ColorSupport::from_env(Some("truecolor"), Some("xterm")), ColorSupport::Full);
+        assert_eq!(ColorSupport::from_env(None, Some("xterm-256color")), ColorSupport::Full);
+        assert_eq!(ColorSupport::from_env(None, Some("linux")), ColorSupport::Basic);
+
+        let mut monitors = test_monitors();
+        monitors[1].enabled = true;
+        let render = |colors: ColorSupport| {
+            let map = Map {
+                colors,
+                ..Map::for_test(&monitors)
+            };
+            let mut buf = Buffer::empty(Rect::new(0, 0, 100, 30));
+            map.render(buf.area, &mut buf);
+            buf
+        };
+        let reversed = |buf: &Buffer| buf.content().iter()
+            .filter(|cell| cell.modifier.contains(Modifier::REVERSED))
+            .map(|cell| cell.symbol().to_string())
+            .collect::<String>();
+        let blue = |buf: &Buffer| buf.content().iter().any(|cell| cell.fg == Color::Blue);
+
+        let full = render(ColorSupport::Full);
+        assert!(blue(&full));
+        assert_eq!(reversed(&full), "");
+
+        // only the selected monitor's label and number stand out
+        let basic = render(ColorSupport::Basic);
+        assert!(!blue(&basic));
+        assert_eq!(reversed(&basic), "Monitor 1 @60Hz1");
+    }
+
+    #[test]
+    fn render_map_native_footprint() {
+        let mut monitors = test_monitors();
+        monitors[0].scale = Some(2.0);
+        let render = |footprint: bool| {
+            let map = Map {
+                footprint,
+                ..Map::for_test(&monitors)
+            };
+            let mut buf = Buffer::empty(Rect::new(0, 0, 100, 30));
+            map.render(buf.area, &mut buf);
+            buf
+        };
+        // width and height in cells of what is drawn in `color`
+        let extent = |buf: &Buffer, color: Color| {
+            let cells: Vec<(u16, u16)> = (0..buf.area.height)
+                .flat_map(|y| (0..buf.area.width).map(move |x| (x, y)))
+                .filter(|&(x, y)| buf[(x, y)].fg == color)
+                .collect();
+            let width = cells.iter().map(|c| c.0).max().unwrap() - cells.iter().map(|c| c.0).min().unwrap() + 1;
+            let height = cells.iter().map(|c| c.1).max().unwrap() - cells.iter().map(|c| c.1).min().unwrap() + 1;
+            (width, height)
+        };
+
+        assert!(!render(false).content().iter().any(|cell| cell.fg == Color::Gray));
+
+        let buf = render(true);
+        let (outline_width, outline_height) = extent(&buf, Color::Gray);
+        let (scaled_width, scaled_height) = extent(&buf, Color::Yellow);
+        assert!(outline_width > scaled_width, "{} <= {}", outline_width, scaled_width);
+        assert!(outline_height > scaled_height, "{} <= {}", outline_height, scaled_height);
+    }
+
+    #[test]
+    fn render_map_grid() {
+        let monitors = test_monitors();
+        let render = |grid: bool| {
+            let map = Map {
+                grid,
+                ..Map::for_test(&monitors)
+            };
+            let mut buf = Buffer::empty(Rect::new(0, 0, 100, 30));
+            map.render(buf.area, &mut buf);
+            buf
+        };
+        let grid_cells = |buf: &Buffer| {
+            buf.content().iter().filter(|cell| cell.fg == Color::DarkGray).count()
+        };
+
+        assert_eq!(grid_cells(&render(false)), 0);
+
+        let buf = render(true);
+        assert!(grid_cells(&buf) > 0);
+        // the x=1000 line is labeled along the top
+        let top = row_text(&buf, 1);
+        assert!(top.contains("1000"), "{}", top);
+    }
+
+    #[test]
+    fn render_map_move_guides() {
+        let render = |mode: TUIMode, x: i32| {
+            let mut monitors = test_monitors();
+            monitors[1].enabled = true;
+            monitors[1].position = Some(crate::monitor::Position { x, y: 2000 });
+            let map = Map {
+                selected: 1,
+                mode,
+                ..Map::for_test(&monitors)
+            };
+            let mut buf = Buffer::empty(Rect::new(0, 0, 100, 30));
+            map.render(buf.area, &mut buf);
+            buf.content().iter().filter(|cell| cell.fg == Color::Magenta).count()
+        };
+
+        // left edge 10px away from the right edge of Monitor 1
+        assert!(render(TUIMode::Move, 1930) > 0);
+        assert_eq!(render(TUIMode::View, 1930), 0);
+        // far from any edge or center
+        assert_eq!(render(TUIMode::Move, 2500), 0);
+    }
 }
@@ -1,9 +1,54 @@
 use crate::rotation::Rotation;
+use crate::utils::ConfigFormat;
+use crate::runner::{CommandRunner, Runner, SystemRunner};
+use crate::logger;
 use serde::{Deserialize, Serialize};
-use std::process::Command;
-use std::io::Write;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
 use ratatui::layout::Rect;
-#[derive(Debug,Default, Clone, Deserialize, Serialize)]
+// wlr-randr run on a background thread, so the TUI can draw while a slow
+// one answers. Polled with try_finish from the tick loop.
+#[derive(Debug)]
+pub struct MonitorDetection {
+    receiver: Receiver<io::Result<String>>,
+}
+
+impl MonitorDetection {
+    // `detect` returns the `wlr-randr --json` output
+    pub fn spawn(detect: impl FnOnce() -> io::Result<String> + Send + 'static) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            // the app may have quit already, nobody to tell then
+            let _ = sender.send(detect());
+        });
+        MonitorDetection { receiver }
+    }
+
+    pub fn wlr_randr() -> Self {
+        MonitorDetection::spawn(|| SystemRunner.run("wlr-randr", &["--json".to_string()]))
+    }
+
+    // None while wlr-randr is still running. No monitors when it failed,
+    // like parse_monitors with an output it can't read.
+    pub fn try_finish(&self) -> Option<Vec<Monitor>> {
+        let stdout = match self.receiver.try_recv() {
+            Ok(stdout) => stdout,
+            Err(TryRecvError::Empty) => return None,
+            Err(TryRecvError::Disconnected) => Err(io::Error::other("monitor detection stopped")),
+        };
+        match stdout {
+            Ok(stdout) => Some(Monitor::parse_monitors(&stdout)),
+            Err(e) => {
+                logger::error(&format!("Failed to run wlr-randr: {}", e));
+                Some(vec![])
+            },
+        }
+    }
+}
+
+#[derive(Debug,Default, Clone, PartialEq, Deserialize, Serialize)]
 pub struct Monitor {
     pub name: String,
     pub description: Option<String>,
@@ -12,58 +57,239 @@ pub struct Monitor {
     pub position: Option<Position>,
     pub scale: Option<f32>,
     pub transform: Option<String>,
+    #[serde(default, rename = "adaptive_sync")]
+    pub vrr: bool,
+    // only some backends report the focused output
+    #[serde(default)]
+    pub focused: bool,
+    #[serde(skip)]
+    pub bit_depth: Option<u8>,
+    #[serde(skip)]
+    pub primary: bool,
+    #[serde(skip)]
+    pub mirror: Option<String>,
+    #[serde(skip)]
+    pub label: Option<String>,
+    // friendly name from the config `aliases`, the configs keep `name`
+    #[serde(skip)]
+    pub alias: Option<String>,
+    // workspace opened on this monitor by default, Hyprland only
+    #[serde(skip)]
+    pub default_workspace: Option<String>,
+    // highest refresh rate picked automatically, to save power
+    #[serde(skip)]
+    pub refresh_cap: Option<f32>,
+    // written in place of the current mode, Hyprland only
+    #[serde(skip)]
+    pub mode_keyword: Option<ModeKeyword>,
+    // position, scale and resolution edits are ignored while set
+    #[serde(skip)]
+    pub locked: bool,
     #[serde(skip)]
     pub saved_position: Option<Position>,
     #[serde(skip)]
     pub saved_scale: Option<f32>,
+    // mode left for the preferred one, see toggle_preferred_mode
+    #[serde(skip)]
+    pub mode_before_preferred: Option<usize>,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq)]
 pub struct Position{
     pub x: i32,
     pub y: i32,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct Resolution {
     pub width: i32,
     pub height: i32,
+    #[serde(deserialize_with = "deserialize_refresh")]
     pub refresh: f32,
     pub preferred: bool,
     pub current: bool,
+    // only picked when selected by hand
+    #[serde(default)]
+    pub interlaced: bool,
+    // not advertised by the monitor, kept from the Hyprland config
+    #[serde(skip)]
+    pub custom: bool,
+}
+
+impl Resolution {
+    // WxH@R, as wlr-randr and the Hyprland config write it
+    pub fn label(&self) -> String {
+        format!("{}x{}@{}", self.width, self.height, self.refresh)
+    }
+
+    // Same size and refresh rate, whatever the flags.
+    pub fn same_mode(&self, other: &Resolution) -> bool {
+        self.width == other.width && self.height == other.height && (self.refresh - other.refresh).abs() < 0.001
+    }
+}
+
+// Some backends report the refresh rate as a string, "59.951" or "60.000 Hz".
+fn deserialize_refresh<'de, D>(deserializer: D) -> Result<f32, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Refresh {
+        Number(f32),
+        Text(String),
+    }
+
+    match Refresh::deserialize(deserializer)? {
+        Refresh::Number(refresh) => Ok(refresh),
+        Refresh::Text(text) => text.trim()
+            .trim_end_matches("Hz")
+            .trim()
+            .parse()
+            .map_err(|_| serde::de::Error::custom(format!("invalid refresh rate `{}`", text))),
+    }
+}
+
+// A `monitor = ...` line read back from a Hyprland config.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HyprlandMonitor {
+    pub name: String,
+    pub enabled: bool,
+    pub resolution: Option<(i32, i32, Option<f32>)>,
+    pub position: Option<Position>,
+    pub scale: Option<f32>,
+    pub transform: Option<String>,
+}
+
+// Hyprland mode shortcuts, written instead of `WxH@R` so the config keeps
+// picking the best mode when the hardware changes
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModeKeyword {
+    HighRes,
+    HighRr,
+}
+
+impl ModeKeyword {
+    pub fn to_hyprland(self) -> &'static str {
+        match self {
+            ModeKeyword::HighRes => "highres",
+            ModeKeyword::HighRr => "highrr",
+        }
+    }
+}
+
+// Terminal cells are about twice as tall as wide
+const CELL_ASPECT: f64 = 2.0;
+// Layout pixels shown when the canvas would be empty otherwise
+const EMPTY_CANVAS_SPAN: f64 = 1000.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Relation {
+    LeftOf,
+    RightOf,
+    Above,
+    Below,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct MonitorCanvas{
-    pub top: i32,
     pub x_bounds: [f64; 2],
     pub y_bounds: [f64; 2],
-    pub offset_y: i32,
+    pub cell_width: f64,
+    pub cell_height: f64,
 }
 
 
 impl Monitor {
 
-    pub fn get_monitors() -> Vec<Monitor> {
-        let output = Command::new("wlr-randr")
-            .arg("--json")
-            .output().expect("Failed to execute wlr-randr command");
-        let stdout = String::from_utf8(output.stdout).expect("Failed to convert output to string");
-        let new_monitors: Vec<Monitor> = match serde_json::from_str(&stdout) {
+    pub fn get_monitors(runner: &Runner) -> Vec<Monitor> {
+        let stdout = runner.run("wlr-randr", &["--json".to_string()])
+            .expect("Failed to execute wlr-randr command");
+        Monitor::parse_monitors(&stdout)
+    }
+
+    // Monitors from a `wlr-randr --json` output.
+    pub fn parse_monitors(stdout: &str) -> Vec<Monitor> {
+        let mut new_monitors: Vec<Monitor> = match serde_json::from_str(stdout) {
             Ok(monitors) => monitors,
             Err(e) => {
-                eprintln!("Deserialization error: {}", e);
+                logger::error(&format!("Deserialization error: {}", e));
                 Vec::new()
             }
         };
+        for monitor in &mut new_monitors {
+            monitor.drop_invalid_modes();
+        }
 
         new_monitors
     }
-    pub fn get_monitors_canvas(monitors: &Vec<Monitor>, _area: &Rect) -> MonitorCanvas {
-        let mut left = 10000.0;
-        let mut bottom = 10000.0;
-        let mut right = -10000.0;
-        let mut top = -10000.0;
+
+    pub fn display_name(&self) -> &str {
+        self.alias.as_deref().unwrap_or(&self.name)
+    }
+
+    // Name of the monitor list row, followed by the mode, scale and
+    // rotation in padded columns when verbose.
+    pub fn list_row_string(&self, verbose: bool) -> String {
+        if !verbose {
+            return self.display_name().to_string();
+        }
+        let mode = self.get_current_resolution()
+            .or_else(|| self.get_prefered_resolution())
+            .map(|m| format!("{}x{}@{}", m.width, m.height, m.refresh))
+            .unwrap_or_else(|| "N/A".to_string());
+        format!(
+            "{:<16} {:<18} x{:<5} [{}]",
+            self.display_name(),
+            mode,
+            self.scale.unwrap_or(1.0),
+            Rotation::from_transform(&self.transform).to_transform(),
+        )
+    }
+
+    // Some virtual outputs report 0Hz (or negative) modes, which can't be set.
+    fn drop_invalid_modes(&mut self) {
+        let count = self.modes.len();
+        self.modes.retain(|m| m.refresh > 0.0);
+        if self.modes.len() < count {
+            logger::debug(&format!("Ignored {} modes without a refresh rate on {}", count - self.modes.len(), self.name));
+        }
+    }
+    // Flashes "index: name" on each enabled physical screen through hyprctl.
+    // Best effort: does nothing when hyprctl is missing or fails.
+    pub fn identify(runner: &Runner, monitors: &[Monitor]) {
+        let focused = runner.run("hyprctl", &["monitors".to_string(), "-j".to_string()])
+            .ok()
+            .and_then(|stdout| serde_json::from_str::<serde_json::Value>(&stdout).ok())
+            .and_then(|value| {
+                value.as_array()?
+                    .iter()
+                    .find(|m| m["focused"].as_bool() == Some(true))?["name"]
+                    .as_str()
+                    .map(|name| name.to_string())
+            });
+
+        let mut batch = vec![];
+        for (index, monitor) in monitors.iter().enumerate() {
+            if !monitor.enabled {
+                continue;
+            }
+            batch.push(format!("dispatch focusmonitor {}", monitor.name));
+            batch.push(format!("notify -1 3000 0 {}: {}", index + 1, monitor.display_name()));
+        }
+        if let Some(name) = focused {
+            batch.push(format!("dispatch focusmonitor {}", name));
+        }
+
+        let _ = runner.run("hyprctl", &["--batch".to_string(), batch.join(" ; ")]);
+    }
+
+    pub fn get_monitors_canvas(monitors: &Vec<Monitor>, area: &Rect, margin: f64) -> MonitorCanvas {
+        let mut left = f64::INFINITY;
+        let mut bottom = f64::INFINITY;
+        let mut right = f64::NEG_INFINITY;
+        let mut top = f64::NEG_INFINITY;
 
         for monitor in monitors {
             if !monitor.enabled {
@@ -73,18 +299,22 @@ impl Monitor {
             if mode.is_none() {
                 mode = monitor.get_prefered_resolution();
             }
+            // every mode dropped by drop_invalid_modes, nothing to size it by
+            let Some(mode) = mode else { continue };
 
             let rotation = Rotation::from_transform(&monitor.transform);
-            let (width, height) = if rotation == Rotation::Deg90 || rotation == Rotation::Deg270 {
-                (mode.unwrap().height, mode.unwrap().width)
+            let (width, height) = if rotation.is_portrait() {
+                (mode.height, mode.width)
             } else {
-                (mode.unwrap().width, mode.unwrap().height)
+                (mode.width, mode.height)
             };
 
-            let monitor_left = monitor.position.clone().unwrap().x as f64;
+            // unset until load_monitors defaults it, drawn at 0x0 meanwhile
+            let position = monitor.position.clone().unwrap_or_default();
+            let monitor_left = position.x as f64;
             let monitor_right = monitor_left  + (width as f64 / monitor.scale.unwrap() as f64);
 
-            let monitor_bottom = monitor.position.clone().unwrap().y as f64;
+            let monitor_bottom = position.y as f64;
             let monitor_top = monitor_bottom + (height as f64 / monitor.scale.unwrap() as f64);
             
             if monitor_right > right {
@@ -101,26 +331,59 @@ impl Monitor {
             }
         }
 
+        // no enabled monitor: keep an empty canvas around the origin
+        if left > right {
+            left = 0.0;
+            bottom = 0.0;
+            right = 0.0;
+            top = 0.0;
+        }
 
-        let margin = 50.0;
         left -= margin;
         bottom -= margin;
         right += margin;
         top += margin;
 
-        let x_bounds = [left, right];
-        let y_bounds = [bottom, top];
+        // nothing to divide into cells without a margin
+        if right - left <= 0.0 {
+            left -= EMPTY_CANVAS_SPAN / 2.0;
+            right += EMPTY_CANVAS_SPAN / 2.0;
+        }
+        if top - bottom <= 0.0 {
+            bottom -= EMPTY_CANVAS_SPAN / 2.0;
+            top += EMPTY_CANVAS_SPAN / 2.0;
+        }
 
-        let mut offset_y = 0.0;
-        if bottom < 0.0 {
-             offset_y = -bottom;
+        // canvas units covered by one terminal cell, borders excluded
+        let inner_width = area.width.saturating_sub(2).max(1) as f64;
+        let mut cell_width = (right - left) / inner_width;
+        let inner_height = area.height.saturating_sub(2).max(1) as f64;
+        let mut cell_height = (top - bottom) / inner_height;
+
+        // Filling the area stretches very wide or tall layouts into slivers,
+        // the short side is padded instead so monitors keep their proportions,
+        // with the layout centered.
+        let stretch = cell_height / (cell_width * CELL_ASPECT);
+        if stretch < 1.0 {
+            let padding = (cell_width * CELL_ASPECT * inner_height - (top - bottom)) / 2.0;
+            bottom -= padding;
+            top += padding;
+            cell_height = (top - bottom) / inner_height;
+        } else if stretch > 1.0 {
+            let padding = (cell_height / CELL_ASPECT * inner_width - (right - left)) / 2.0;
+            left -= padding;
+            right += padding;
+            cell_width = (right - left) / inner_width;
         }
+
+        let x_bounds = [left, right];
+        let y_bounds = [bottom, top];
        
         MonitorCanvas {
-            top: top as i32,
             x_bounds,
             y_bounds,
-            offset_y: offset_y as i32,
+            cell_width,
+            cell_height,
         }
 
     }
@@ -137,6 +400,169 @@ impl Monitor {
             .find(|m| m.preferred)
     }
     
+    // Marks the preferred mode as current when the compositor reported none,
+    // or the largest one when there is no preferred mode either. With a
+    // refresh cap, the fastest mode of the preferred size under it.
+    pub fn ensure_current_resolution(&mut self) {
+        if self.get_current_resolution().is_some() {
+            return;
+        }
+        let index = match self.modes.iter().position(|m| m.preferred) {
+            Some(preferred) if self.refresh_cap.is_some() => {
+                let mode = &self.modes[preferred];
+                self.fastest_mode(mode.width, mode.height).or(Some(preferred))
+            },
+            Some(preferred) => Some(preferred),
+            None => self.sorted_mode_indices().first().copied(),
+        };
+        if let Some(index) = index {
+            self.modes[index].current = true;
+        }
+    }
+
+    // Indices into `modes`, largest resolution and highest refresh first.
+    pub fn sorted_mode_indices(&self) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..self.modes.len()).collect();
+        indices.sort_by(|&a, &b| {
+            let (a, b) = (&self.modes[a], &self.modes[b]);
+            b.width.cmp(&a.width)
+                .then(b.height.cmp(&a.height))
+                .then(b.refresh.total_cmp(&a.refresh))
+                .then(a.interlaced.cmp(&b.interlaced))
+        });
+        indices
+    }
+
+    // Highest refresh mode at the current (or preferred) resolution.
+    pub fn max_refresh_mode(&self) -> Option<usize> {
+        let mut mode = self.get_current_resolution();
+        if mode.is_none() {
+            mode = self.get_prefered_resolution();
+        }
+        let (width, height) = mode.map(|m| (m.width, m.height))?;
+        self.fastest_mode(width, height)
+    }
+
+    // Highest refresh rate progressive mode of that size, at most refresh_cap
+    // when set (half a Hz over so a 60 cap takes 60.02).
+    fn fastest_mode(&self, width: i32, height: i32) -> Option<usize> {
+        self.modes
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| m.width == width && m.height == height && !m.interlaced)
+            .filter(|(_, m)| self.refresh_cap.is_none_or(|cap| m.refresh <= cap + 0.5))
+            .max_by(|(_, a), (_, b)| a.refresh.total_cmp(&b.refresh))
+            .map(|(i, _)| i)
+    }
+
+    // Progressive modes win over an interlaced one with the same timings.
+    pub fn find_mode(&self, width: i32, height: i32, refresh: f32) -> Option<usize> {
+        self.modes
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| m.width == width && m.height == height && (m.refresh - refresh).abs() < 0.001)
+            .min_by_key(|(_, m)| m.interlaced)
+            .map(|(i, _)| i)
+    }
+
+    // Compares the selected modes with the ones `live` (a new detection)
+    // runs, one line per monitor where they differ. The running mode is
+    // selected in its place so the list shows what took effect.
+    pub fn reconcile_modes(monitors: &mut [Monitor], live: &[Monitor]) -> Vec<String> {
+        let mut lines = vec![];
+        for monitor in monitors.iter_mut().filter(|m| m.enabled) {
+            let Some(running) = live.iter()
+                .find(|l| l.name == monitor.name && l.enabled)
+                .and_then(|l| l.get_current_resolution())
+            else { continue };
+            let Some(selected) = monitor.get_current_resolution() else { continue };
+            if selected.same_mode(running) {
+                continue;
+            }
+            lines.push(format!("{}: selected {}, running {}", monitor.display_name(), selected.label(), running.label()));
+            if let Some(index) = monitor.find_mode(running.width, running.height, running.refresh) {
+                monitor.set_current_resolution(index);
+            }
+        }
+        lines
+    }
+
+    // What `live` (a detection after applying) runs differently from
+    // `monitors`, one line per setting. Nothing is changed on either side.
+    pub fn verify_live(monitors: &[Monitor], live: &[Monitor]) -> Vec<String> {
+        let label = |m: Option<&Resolution>| m.map(Resolution::label).unwrap_or_else(|| "N/A".to_string());
+        let mut lines = vec![];
+        for monitor in monitors {
+            let name = monitor.display_name();
+            let running = live.iter().find(|l| l.name == monitor.name).filter(|l| l.enabled);
+            let Some(running) = running else {
+                if monitor.enabled {
+                    lines.push(format!("{}: not enabled", name));
+                }
+                continue;
+            };
+            if !monitor.enabled {
+                lines.push(format!("{}: still enabled", name));
+                continue;
+            }
+            let (mode, live_mode) = (monitor.get_current_resolution(), running.get_current_resolution());
+            let same_mode = match (mode, live_mode) {
+                (Some(mode), Some(live_mode)) => mode.same_mode(live_mode),
+                (mode, live_mode) => mode.is_none() && live_mode.is_none(),
+            };
+            if !same_mode {
+                lines.push(format!("{}: mode {}, running {}", name, label(mode), label(live_mode)));
+            }
+            let (position, live_position) = (monitor.position.clone().unwrap_or_default(), running.position.clone().unwrap_or_default());
+            if position != live_position {
+                lines.push(format!("{}: position {}x{}, running {}x{}", name, position.x, position.y, live_position.x, live_position.y));
+            }
+            let (scale, live_scale) = (monitor.scale.unwrap_or(1.0), running.scale.unwrap_or(1.0));
+            if (scale - live_scale).abs() > 0.001 {
+                lines.push(format!("{}: scale {}, running {}", name, scale, live_scale));
+            }
+            let (rotation, live_rotation) = (Rotation::from_transform(&monitor.transform), Rotation::from_transform(&running.transform));
+            if rotation != live_rotation {
+                lines.push(format!("{}: rotation {}, running {}", name, rotation.to_transform(), live_rotation.to_transform()));
+            }
+        }
+        lines
+    }
+
+    // Copies scale, resolution, transform and bit depth from another monitor.
+    // Returns false, leaving the monitor untouched, if the resolution is not supported.
+    pub fn copy_settings_from(&mut self, source: &Monitor) -> bool {
+        let mut mode = source.get_current_resolution();
+        if mode.is_none() {
+            mode = source.get_prefered_resolution();
+        }
+        let index = match mode {
+            Some(res) => match self.find_mode(res.width, res.height, res.refresh) {
+                Some(index) => index,
+                None => return false,
+            },
+            None => return false,
+        };
+        self.set_current_resolution(index);
+        self.scale = source.scale;
+        self.transform = source.transform.clone();
+        self.bit_depth = source.bit_depth;
+        true
+    }
+
+    // Flips between the preferred mode and the one selected before it, so
+    // repeated presses compare the two.
+    pub fn toggle_preferred_mode(&mut self) {
+        let current = self.modes.iter().position(|m| m.current);
+        let Some(preferred) = self.modes.iter().position(|m| m.preferred) else { return };
+        if current != Some(preferred) {
+            self.mode_before_preferred = current;
+            self.set_current_resolution(preferred);
+        } else if let Some(previous) = self.mode_before_preferred.take() {
+            self.set_current_resolution(previous);
+        }
+    }
+
     pub fn set_current_resolution(&mut self, index: usize) {
         if index < self.modes.len() {
             for mode in &mut self.modes {
@@ -144,36 +570,269 @@ impl Monitor {
             }
             self.modes[index].current = true;
         } else {
-            eprintln!("Index out of bounds: {}", index);
+            logger::warn(&format!("Index out of bounds: {}", index));
         }
     }
 
-    pub fn to_hyprland_config(&self) -> String {
-        let mode = match self.get_current_resolution() {
-            Some(m) => m,
-            None => {
-                self.get_prefered_resolution().expect("No preferred resolution found")
+    // `minimal` drops the transform when the monitor isn't rotated.
+    // The scale is positional in Hyprland, so it is always written.
+    pub fn to_hyprland_config(&self, minimal: bool) -> String {
+        let mode = self.get_current_resolution().or_else(|| self.get_prefered_resolution());
+        if self.enabled {
+            let rotation = Rotation::from_transform(&self.transform);
+            // unset until load_monitors defaults it, written as 0x0 meanwhile
+            let position = self.position.clone().unwrap_or_default();
+            let mut line = format!(
+                "monitor = {}, {}, {}x{}, {}",
+                self.name,
+                self.hyprland_mode(mode),
+                position.x, position.y,
+                self.scale.unwrap_or(1.0),
+            );
+            if !minimal || rotation != Rotation::Normal {
+                line.push_str(&format!(", transform,{}", rotation.to_hyprland()));
             }
-        };
+            if self.vrr {
+                line.push_str(", vrr,1");
+            }
+            if let Some(bit_depth) = self.bit_depth {
+                line.push_str(&format!(", bitdepth,{}", bit_depth));
+            }
+            if let Some(mirror) = &self.mirror {
+                line.push_str(&format!(", mirror,{}", mirror));
+            }
+            line
+        } else {
+            format!(
+                "monitor = {}, disabled",
+                self.name
+            )
+        }
+        
+    }
+
+    // Hyprland picks the mode itself for an output without a usable one.
+    fn hyprland_mode(&self, mode: Option<&Resolution>) -> String {
+        match (self.mode_keyword, mode) {
+            (Some(keyword), _) => keyword.to_hyprland().to_string(),
+            (None, Some(mode)) => format!("{}x{}@{}", mode.width, mode.height, mode.refresh),
+            (None, None) => "preferred".to_string(),
+        }
+    }
+
+    // Same values as `to_hyprland_config`, in the named-key block syntax.
+    pub fn to_hyprland_v2_config(&self, minimal: bool) -> String {
+        let mut fields = vec![format!("output = {}", self.name)];
         if self.enabled {
+            let mode = self.get_current_resolution().or_else(|| self.get_prefered_resolution());
             let rotation = Rotation::from_transform(&self.transform);
+            let position = self.position.clone().unwrap_or_default();
+            fields.push(format!("mode = {}", self.hyprland_mode(mode)));
+            fields.push(format!("position = {}x{}", position.x, position.y));
+            fields.push(format!("scale = {}", self.scale.unwrap_or(1.0)));
+            if !minimal || rotation != Rotation::Normal {
+                fields.push(format!("transform = {}", rotation.to_hyprland()));
+            }
+            if self.vrr {
+                fields.push("vrr = 1".to_string());
+            }
+            if let Some(bit_depth) = self.bit_depth {
+                fields.push(format!("bitdepth = {}", bit_depth));
+            }
+            if let Some(mirror) = &self.mirror {
+                fields.push(format!("mirror = {}", mirror));
+            }
+        } else {
+            fields.push("disabled = true".to_string());
+        }
+        format!("monitorv2 {{\n{}\n}}", fields.iter().map(|f| format!("    {}", f)).collect::<Vec<_>>().join("\n"))
+    }
+
+    // Companion rule for `default_workspace`, written after the monitor config
+    pub fn to_hyprland_workspace(&self) -> Option<String> {
+        let workspace = self.default_workspace.as_ref().filter(|_| self.enabled)?;
+        Some(format!("workspace = {}, monitor:{}", workspace, self.name))
+    }
+
+    // Same as the config line, as a `hyprctl keyword` command
+    pub fn to_hyprland_keyword(&self) -> String {
+        self.to_hyprland_config(false).replacen("monitor = ", "keyword monitor ", 1)
+    }
+
+    // Applies the layout to the running compositor without a reload
+    pub fn apply_hyprland(runner: &Runner, monitors: &[Monitor]) -> std::io::Result<String> {
+        let batch: Vec<String> = monitors.iter().map(|m| m.to_hyprland_keyword()).collect();
+        runner.run("hyprctl", &["--batch".to_string(), batch.join(" ; ")])
+    }
+
+    pub fn to_sway_config(&self) -> String {
+        let mode = self.get_current_resolution().or_else(|| self.get_prefered_resolution());
+        if self.enabled {
+            let rotation = Rotation::from_transform(&self.transform);
+            let position = self.position.clone().unwrap_or_default();
+            // sway keeps the output's own mode when none is given
+            let mode = mode
+                .map(|m| format!(" mode {}x{}@{}Hz", m.width, m.height, m.refresh))
+                .unwrap_or_default();
             format!(
-                "monitor = {}, {}x{}@{}, {}x{}, {}, transform,{}",
+                "output {}{} pos {} {} scale {} transform {}",
                 self.name,
-                mode.width, mode.height, mode.refresh,
-                self.position.clone().unwrap().x, self.position.clone().unwrap().y,
+                mode,
+                position.x, position.y,
                 self.scale.unwrap_or(1.0),
-                rotation.to_hyprland()
+                rotation.to_transform()
             )
         } else {
             format!(
-                "monitor = {}, disabled",
+                "output {} disable",
                 self.name
             )
         }
-        
     }
-    pub fn save_hyprland_config(path:&String,monitors: &Vec<Monitor>) -> std::io::Result<()> {
+
+    pub fn to_niri_config(&self) -> String {
+        let mode = self.get_current_resolution().or_else(|| self.get_prefered_resolution());
+        if self.enabled {
+            let rotation = Rotation::from_transform(&self.transform);
+            let position = self.position.clone().unwrap_or_default();
+            // niri keeps the output's own mode when none is given
+            let mode = mode
+                .map(|m| format!("\n    mode \"{}x{}@{:.3}\"", m.width, m.height, m.refresh))
+                .unwrap_or_default();
+            format!(
+                "output \"{}\" {{{}\n    scale {}\n    transform \"{}\"\n    position x={} y={}\n}}",
+                self.name,
+                mode,
+                self.scale.unwrap_or(1.0),
+                rotation.to_transform(),
+                position.x, position.y
+            )
+        } else {
+            format!(
+                "output \"{}\" {{\n    off\n}}",
+                self.name
+            )
+        }
+    }
+
+    pub fn to_config(&self, format: ConfigFormat, minimal: bool) -> String {
+        match format {
+            ConfigFormat::Hyprland => self.to_hyprland_config(minimal),
+            ConfigFormat::HyprlandV2 => self.to_hyprland_v2_config(minimal),
+            ConfigFormat::Sway => self.to_sway_config(),
+            ConfigFormat::Niri => self.to_niri_config(),
+        }
+    }
+
+    pub fn parse_hyprland_config(line: &str) -> Option<HyprlandMonitor> {
+        let (keyword, value) = line.split_once('=')?;
+        if keyword.trim() != "monitor" {
+            return None;
+        }
+        let fields: Vec<&str> = value.split('#').next()?.split(',').map(|f| f.trim()).collect();
+        let name = fields.first().filter(|name| !name.is_empty())?.to_string();
+        let mut monitor = HyprlandMonitor {
+            name,
+            enabled: fields.get(1) != Some(&"disabled"),
+            resolution: None,
+            position: None,
+            scale: None,
+            transform: None,
+        };
+        if !monitor.enabled {
+            return Some(monitor);
+        }
+
+        // "preferred", "auto", ... leave the value unset
+        monitor.resolution = fields.get(1).and_then(|res| {
+            let (size, refresh) = match res.split_once('@') {
+                Some((size, refresh)) => (size, refresh.trim_end_matches("Hz").parse().ok()),
+                None => (*res, None),
+            };
+            let (width, height) = size.split_once('x')?;
+            Some((width.parse().ok()?, height.parse().ok()?, refresh))
+        });
+        monitor.position = fields.get(2).and_then(|pos| {
+            let (x, y) = pos.split_once('x')?;
+            Some(Position { x: x.parse().ok()?, y: y.parse().ok()? })
+        });
+        monitor.scale = fields.get(3).and_then(|scale| scale.parse().ok());
+        monitor.transform = fields.iter()
+            .position(|f| *f == "transform")
+            .and_then(|i| fields.get(i + 1)?.parse().ok())
+            .map(|t| Rotation::from_hyprland(t).to_transform().to_string());
+        Some(monitor)
+    }
+
+    // Follows the `source = ...` lines of a split config, one level deep.
+    pub fn load_hyprland_config(path: &str) -> Option<Vec<HyprlandMonitor>> {
+        let read = |path: &str| std::fs::read_to_string(shellexpand::tilde(path).as_ref()).ok();
+        let content = read(path)?;
+        let mut monitors = vec![];
+        for line in content.lines() {
+            let source = line.trim()
+                .strip_prefix("source")
+                .and_then(|rest| rest.trim_start().strip_prefix('='));
+            match source {
+                Some(source) => monitors.extend(
+                    read(source.trim()).unwrap_or_default().lines().filter_map(Monitor::parse_hyprland_config)
+                ),
+                None => monitors.extend(Monitor::parse_hyprland_config(line)),
+            }
+        }
+        Some(monitors)
+    }
+
+    pub fn apply_hyprland_config(&mut self, config: &HyprlandMonitor) {
+        if config.enabled {
+            self.enable();
+        } else {
+            self.disable();
+        }
+        if let Some((width, height, refresh)) = config.resolution {
+            let index = match refresh {
+                Some(refresh) => self.find_mode(width, height, refresh),
+                None => self.modes.iter().position(|m| m.width == width && m.height == height),
+            };
+            match (index, refresh) {
+                (Some(index), _) => self.set_current_resolution(index),
+                (None, Some(refresh)) => {
+                    self.modes.push(Resolution {
+                        width,
+                        height,
+                        refresh,
+                        preferred: false,
+                        current: false,
+                        interlaced: false,
+                        custom: true,
+                    });
+                    self.set_current_resolution(self.modes.len() - 1);
+                },
+                (None, None) => {},
+            }
+        }
+        if config.position.is_some() {
+            self.position = config.position.clone();
+        }
+        if config.scale.is_some() {
+            self.scale = config.scale;
+        }
+        if config.transform.is_some() {
+            self.transform = config.transform.clone();
+        }
+    }
+
+    // The monitor's config followed by its workspace rule, if any
+    fn config_lines(&self, format: ConfigFormat, minimal: bool) -> Vec<String> {
+        let mut lines = vec![self.to_config(format, minimal)];
+        if matches!(format, ConfigFormat::Hyprland | ConfigFormat::HyprlandV2)
+            && let Some(workspace_line) = self.to_hyprland_workspace() {
+            lines.push(workspace_line);
+        }
+        lines
+    }
+
+    pub fn save_config(path:&str,monitors: &[Monitor],format: ConfigFormat,minimal: bool) -> std::io::Result<()> {
         let expanded_path = shellexpand::tilde(path).to_string();
         let mut file = std::fs::OpenOptions::new()
             .write(true)
@@ -181,12 +840,139 @@ impl Monitor {
             .create(true)
             .open(expanded_path)?;
         for monitor in monitors {
-            let config_line = monitor.to_hyprland_config();
-            writeln!(file, "{}", config_line)?;
+            for line in monitor.config_lines(format, minimal) {
+                writeln!(file, "{}", line)?;
+            }
         }
         Ok(())
     }
 
+    // `monitors/<name>.conf` next to the config at `path`
+    pub fn include_path(path: &str, name: &str) -> PathBuf {
+        let expanded_path = PathBuf::from(shellexpand::tilde(path).as_ref());
+        expanded_path.parent()
+            .unwrap_or(Path::new(""))
+            .join("monitors")
+            .join(format!("{}.conf", name.replace('/', "_")))
+    }
+
+    // Writes only this monitor's include file, see save_split_config.
+    pub fn save_include(&self, path: &str, format: ConfigFormat, minimal: bool) -> std::io::Result<()> {
+        let include = Monitor::include_path(path, &self.name);
+        if let Some(dir) = include.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(include, self.config_lines(format, minimal).join("\n") + "\n")
+    }
+
+    // Each monitor in its own include file, `path` only sources them.
+    pub fn save_split_config(path: &str, monitors: &[Monitor], format: ConfigFormat, minimal: bool) -> std::io::Result<()> {
+        let mut index = String::new();
+        for monitor in monitors {
+            monitor.save_include(path, format, minimal)?;
+            index.push_str(&format!("source = {}\n", Monitor::include_path(path, &monitor.name).display()));
+        }
+        std::fs::write(shellexpand::tilde(path).as_ref(), index)
+    }
+
+    // `content` with this monitor's `monitor = ...` line replaced, keeping a
+    // trailing comment, or appended when it has none. Other lines are kept as is.
+    pub fn replace_hyprland_config_line(&self, content: &str, minimal: bool) -> String {
+        let config_line = self.to_hyprland_config(minimal);
+        let mut found = false;
+        let mut lines: Vec<String> = content.lines()
+            .map(|line| {
+                if Monitor::parse_hyprland_config(line).is_none_or(|m| m.name != self.name) {
+                    return line.to_string();
+                }
+                found = true;
+                match line.split_once('#') {
+                    Some((_, comment)) => format!("{} #{}", config_line, comment),
+                    None => config_line.clone(),
+                }
+            })
+            .collect();
+        if !found {
+            lines.push(config_line);
+        }
+        lines.join("\n") + "\n"
+    }
+
+    // Writes only this monitor's line to the Hyprland config at `path`.
+    pub fn save_config_line(&self, path: &str, minimal: bool) -> std::io::Result<()> {
+        let expanded_path = shellexpand::tilde(path).to_string();
+        let content = match std::fs::read_to_string(&expanded_path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+        std::fs::write(expanded_path, self.replace_hyprland_config_line(&content, minimal))
+    }
+
+    pub fn disable(&mut self) {
+        if self.enabled {
+            self.saved_position = self.position.clone();
+            self.saved_scale = self.scale;
+        }
+        self.enabled = false;
+    }
+
+    pub fn enable(&mut self) {
+        self.enabled = true;
+        // If no saved_position (from disable), keep the current position
+        // which might have been loaded from the persistent state file
+        self.position = self.saved_position.take()
+            .or(self.position.take())
+            .or(Some(Position { x: 0, y: 0 }));
+        self.scale = self.saved_scale.take().or(self.scale).or(Some(1.0));
+    }
+
+    // Moves the enabled, unlocked monitors right of `index`, on the same row,
+    // left by its width. `open_gap` moves them back once it is enabled again.
+    pub fn close_gap(monitors: &mut [Monitor], index: usize) {
+        Monitor::shift_right_of(monitors, index, -1.0);
+    }
+
+    pub fn open_gap(monitors: &mut [Monitor], index: usize) {
+        Monitor::shift_right_of(monitors, index, 1.0);
+    }
+
+    fn shift_right_of(monitors: &mut [Monitor], index: usize, direction: f64) {
+        if monitors[index].position.is_none() {
+            return;
+        }
+        let (x, y, w, h) = monitors[index].get_geometry();
+        // re-enabled: the others start where it used to end
+        let start = if direction > 0.0 { x } else { x + w };
+        let shift = (direction * w).round() as i32;
+        for (i, monitor) in monitors.iter_mut().enumerate() {
+            if i == index || !monitor.enabled || monitor.locked || monitor.position.is_none() {
+                continue;
+            }
+            let (mx, my, _, mh) = monitor.get_geometry();
+            if mx >= start && my < y + h && my + mh > y {
+                monitor.move_horizontal(shift);
+            }
+        }
+    }
+
+    // Moves the other enabled, unlocked monitors up or down so their top
+    // edges (bottom edges with `bottom`) line up with the one at `index`.
+    pub fn align_edges(monitors: &mut [Monitor], index: usize, bottom: bool) {
+        if monitors[index].position.is_none() {
+            return;
+        }
+        let (_, y, _, h) = monitors[index].get_geometry();
+        for (i, monitor) in monitors.iter_mut().enumerate() {
+            if i == index || !monitor.enabled || monitor.locked || monitor.position.is_none() {
+                continue;
+            }
+            let (_, my, _, mh) = monitor.get_geometry();
+            let target = if bottom { y + h - mh } else { y };
+            monitor.move_vertical((target - my).round() as i32);
+        }
+    }
+
     pub fn move_vertical(&mut self, direction: i32) {
         if let Some(ref mut pos) = self.position { pos.y += direction};
     }
@@ -195,6 +981,193 @@ impl Monitor {
         if let Some(ref mut pos) = self.position { pos.x += direction};
     }
 
+    // Moves the monitor so that it touches `target` on the given side,
+    // aligned on the target top (left/right) or left (above/below) edge.
+    pub fn place_relative(&mut self, target: &Monitor, relation: Relation) {
+        let (_, _, w, h) = self.get_geometry();
+        let (tx, ty, tw, th) = target.get_geometry();
+        let (x, y) = match relation {
+            Relation::LeftOf => (tx - w, ty),
+            Relation::RightOf => (tx + tw, ty),
+            Relation::Above => (tx, ty - h),
+            Relation::Below => (tx, ty + th),
+        };
+        self.position = Some(Position { x: x.round() as i32, y: y.round() as i32 });
+    }
+
+    // The primary monitor, else the enabled one at 0x0, else the first enabled.
+    pub fn primary_index(monitors: &[Monitor]) -> Option<usize> {
+        monitors.iter().position(|m| m.primary)
+            .or_else(|| monitors.iter().position(|m| m.enabled && m.position.clone().unwrap_or_default() == Position::default()))
+            .or_else(|| monitors.iter().position(|m| m.enabled))
+    }
+
+    // Index of the enabled monitor whose center is closest to the one at `index`.
+    pub fn nearest(monitors: &[Monitor], index: usize) -> Option<usize> {
+        let (x, y, w, h) = monitors[index].get_geometry();
+        let (cx, cy) = (x + w / 2.0, y + h / 2.0);
+        monitors.iter()
+            .enumerate()
+            .filter(|(i, m)| *i != index && m.enabled)
+            .map(|(i, m)| {
+                let (ox, oy, ow, oh) = m.get_geometry();
+                let (dx, dy) = (ox + ow / 2.0 - cx, oy + oh / 2.0 - cy);
+                (i, dx * dx + dy * dy)
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+    }
+
+    // Index of the enabled monitor closest to the one at `index` on the given side,
+    // comparing centers. Only monitors more ahead than sideways count, and sideways
+    // offsets count double so aligned neighbors win.
+    pub fn neighbor(monitors: &[Monitor], index: usize, relation: Relation) -> Option<usize> {
+        let (x, y, w, h) = monitors[index].get_geometry();
+        let (cx, cy) = (x + w / 2.0, y + h / 2.0);
+        monitors.iter()
+            .enumerate()
+            .filter(|(i, m)| *i != index && m.enabled)
+            .filter_map(|(i, m)| {
+                let (ox, oy, ow, oh) = m.get_geometry();
+                let (dx, dy) = (ox + ow / 2.0 - cx, oy + oh / 2.0 - cy);
+                let (ahead, sideways) = match relation {
+                    Relation::LeftOf => (-dx, dy),
+                    Relation::RightOf => (dx, dy),
+                    Relation::Above => (-dy, dx),
+                    Relation::Below => (dy, dx),
+                };
+                (ahead > 0.0 && ahead >= sideways.abs()).then_some((i, ahead * ahead + 4.0 * sideways * sideways))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+    }
+
+    // Hyprland rejects scales that don't divide the mode into whole logical pixels.
+    pub fn has_integer_logical_size(&self) -> bool {
+        let (_, _, w, h) = self.get_geometry();
+        (w - w.round()).abs() < 0.001 && (h - h.round()).abs() < 0.001
+    }
+
+    pub fn overlaps(&self, other: &Monitor) -> bool {
+        let (x, y, w, h) = self.get_geometry();
+        let (ox, oy, ow, oh) = other.get_geometry();
+        x < ox + ow && ox < x + w && y < oy + oh && oy < y + h
+    }
+
+    // Shares a stretch of edge with `other`, corners alone don't count.
+    pub fn touches(&self, other: &Monitor) -> bool {
+        let (x, y, w, h) = self.get_geometry();
+        let (ox, oy, ow, oh) = other.get_geometry();
+        let flush = |a: f64, b: f64| (a - b).abs() < 0.5;
+        let side = (flush(x + w, ox) || flush(ox + ow, x)) && y < oy + oh && oy < y + h;
+        let stacked = (flush(y + h, oy) || flush(oy + oh, y)) && x < ox + ow && ox < x + w;
+        side || stacked
+    }
+
+    // Enabled monitors touching none of the others, the cursor can't reach them.
+    pub fn gap_warnings(monitors: &[Monitor]) -> Vec<String> {
+        let enabled: Vec<&Monitor> = monitors.iter().filter(|m| m.enabled).collect();
+        if enabled.len() < 2 {
+            return vec![];
+        }
+        enabled.iter()
+            .filter(|m| !enabled.iter().any(|o| !std::ptr::eq(*m, o) && (m.touches(o) || m.overlaps(o))))
+            .map(|m| format!("{} doesn't touch any other display", m.display_name()))
+            .collect()
+    }
+
+    pub fn negative_position_warning(&self) -> Option<String> {
+        let position = self.position.clone().filter(|p| p.x < 0 || p.y < 0)?;
+        Some(format!("{} is at ({},{}), left of or above 0x0", self.display_name(), position.x, position.y))
+    }
+
+    pub fn fractional_scale_warning(&self) -> Option<String> {
+        if self.has_integer_logical_size() {
+            return None;
+        }
+        let (_, _, w, h) = self.get_geometry();
+        Some(format!(
+            "{} at scale {} is {:.2}x{:.2} logical pixels, Hyprland wants whole ones",
+            self.display_name(), self.scale.unwrap_or(1.0), w, h
+        ))
+    }
+
+    // Every check at once, before writing: the layout warnings shown under
+    // the map plus gaps, negative positions and scales Hyprland rejects.
+    pub fn validate(monitors: &[Monitor]) -> Vec<String> {
+        let enabled: Vec<&Monitor> = monitors.iter().filter(|m| m.enabled).collect();
+        let mut issues = Monitor::layout_warnings(monitors);
+        issues.extend(Monitor::gap_warnings(monitors));
+        issues.extend(enabled.iter().filter_map(|m| m.negative_position_warning()));
+        issues.extend(enabled.iter().filter_map(|m| m.fractional_scale_warning()));
+        issues
+    }
+
+    pub fn layout_warnings(monitors: &[Monitor]) -> Vec<String> {
+        let mut warnings = vec![];
+        let enabled: Vec<&Monitor> = monitors.iter().filter(|m| m.enabled).collect();
+        for (i, monitor) in enabled.iter().enumerate() {
+            for other in &enabled[i + 1..] {
+                if monitor.position.is_some() && monitor.position == other.position {
+                    let pos = monitor.position.clone().unwrap();
+                    warnings.push(format!(
+                        "{} and {} are stacked at ({},{})",
+                        monitor.display_name(), other.display_name(), pos.x, pos.y
+                    ));
+                } else if monitor.overlaps(other) {
+                    warnings.push(format!("{} overlaps {}", monitor.display_name(), other.display_name()));
+                }
+            }
+        }
+        warnings.extend(enabled.iter().filter_map(|m| m.bandwidth_warning()));
+        warnings.extend(enabled.iter().filter_map(|m| m.custom_mode_warning()));
+        warnings
+    }
+
+    // The current mode isn't one the monitor advertises, Hyprland may refuse it.
+    pub fn custom_mode_warning(&self) -> Option<String> {
+        let mode = self.get_current_resolution().filter(|m| m.custom)?;
+        let advertised = self.modes.iter()
+            .any(|m| !m.custom && m.width == mode.width && m.height == mode.height && m.refresh == mode.refresh);
+        if advertised {
+            return None;
+        }
+        Some(format!(
+            "{} uses {}x{}@{}, not one of its modes",
+            self.display_name(), mode.width, mode.height, mode.refresh
+        ))
+    }
+
+    // Advisory only: a cursor sized for one scale looks too small or too big
+    // on a display with another one.
+    pub fn mixed_scale_note(monitors: &[Monitor]) -> Option<String> {
+        let mut scales = monitors.iter()
+            .filter(|m| m.enabled)
+            .map(|m| m.scale.unwrap_or(1.0));
+        let first = scales.next()?;
+        if scales.all(|scale| scale == first) {
+            return None;
+        }
+        Some("Displays use different scales, set the cursor size once (e.g. HYPRCURSOR_SIZE) so it matches on each".to_string())
+    }
+
+    // Uncompressed video data rate of the current mode at the chosen bit depth
+    // against DisplayPort 1.4 (HBR3), the usual link limit. Only checked once
+    // a bit depth was set.
+    pub fn bandwidth_warning(&self) -> Option<String> {
+        const LINK_LIMIT_GBPS: f64 = 25.92;
+        let bit_depth = self.bit_depth?;
+        let mode = self.get_current_resolution()?;
+        let required = mode.width as f64 * mode.height as f64 * mode.refresh as f64 * bit_depth as f64 * 3.0 / 1e9;
+        if required <= LINK_LIMIT_GBPS {
+            return None;
+        }
+        Some(format!(
+            "{} {}x{}@{} at {}-bit needs {:.1} Gbit/s, over the {} of DisplayPort 1.4: lower the refresh rate or the bit depth",
+            self.display_name(), mode.width, mode.height, mode.refresh, bit_depth, required, LINK_LIMIT_GBPS
+        ))
+    }
+
     pub fn get_geometry(&self) -> (f64, f64, f64, f64) {
         let mut mode = self.get_current_resolution();
         if mode.is_none() {
@@ -204,7 +1177,7 @@ impl Monitor {
         if mode.is_none() { return (0.0,0.0,0.0,0.0); }
 
         let rotation = Rotation::from_transform(&self.transform);
-        let (width, height) = if rotation == Rotation::Deg90 || rotation == Rotation::Deg270 {
+        let (width, height) = if rotation.is_portrait() {
             (mode.unwrap().height, mode.unwrap().width)
         } else {
             (mode.unwrap().width, mode.unwrap().height)
@@ -213,9 +1186,679 @@ impl Monitor {
         let scale = self.scale.unwrap_or(1.0);
         let logical_width = width as f64 / scale as f64;
         let logical_height = height as f64 / scale as f64;
-        let x = self.position.clone().unwrap().x as f64;
-        let y = self.position.clone().unwrap().y as f64;
+        let position = self.position.clone().unwrap_or_default();
+
+        (position.x as f64, position.y as f64, logical_width, logical_height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+    use crate::test_utils::tests::{test_monitors, FakeRunner};
+
+    #[test]
+    fn validate_broken_layout() {
+        let mut monitors = test_monitors();
+        monitors[1].enabled = true;
+        // Monitor 2 is 1024x576 at 1.25: under Monitor 1
+        monitors[1].position = Some(Position { x: 100, y: 100 });
+        let mut far = test_monitors()[0].clone();
+        far.name = "Far".to_string();
+        far.position = Some(Position { x: -4000, y: 0 });
+        far.scale = Some(1.3);
+        far.modes[0].custom = true;
+        far.modes[0].width = 1921;
+        monitors.push(far);
+
+        let issues = Monitor::validate(&monitors);
+        assert_eq!(issues, vec![
+            "Monitor 1 overlaps Monitor 2".to_string(),
+            "Far uses 1921x1080@60, not one of its modes".to_string(),
+            "Far doesn't touch any other display".to_string(),
+            "Far is at (-4000,0), left of or above 0x0".to_string(),
+            "Far at scale 1.3 is 1477.69x830.77 logical pixels, Hyprland wants whole ones".to_string(),
+        ]);
+
+        assert!(Monitor::validate(&test_monitors()).is_empty());
+    }
+
+    #[test]
+    fn list_row_string_verbose() {
+        let mut monitors = test_monitors();
+        assert_eq!(monitors[1].list_row_string(false), "Monitor 2");
+        assert_eq!(
+            monitors[1].list_row_string(true),
+            "Monitor 2        1280x720@60        x1.25  [normal]"
+        );
+
+        monitors[0].transform = Some("90".to_string());
+        monitors[0].modes.clear();
+        assert_eq!(
+            monitors[0].list_row_string(true),
+            "Monitor 1        N/A                x1     [90]"
+        );
+    }
+
+    #[test]
+    fn get_monitors_from_wlr_randr() {
+        let stdout = r#"[
+            {
+                "name": "DP-1",
+                "description": "Dell U2720Q",
+                "make": "Dell",
+                "enabled": true,
+                "modes": [
+                    {"width": 3840, "height": 2160, "refresh": 59.997, "preferred": true, "current": true},
+                    {"width": 1920, "height": 1080, "refresh": 60.0, "preferred": false, "current": false}
+                ],
+                "position": {"x": 0, "y": 0},
+                "transform": "normal",
+                "scale": 1.5,
+                "adaptive_sync": true
+            },
+            {
+                "name": "HDMI-A-1",
+                "description": null,
+                "enabled": false,
+                "modes": []
+            }
+        ]"#;
+        let runner = Rc::new(FakeRunner::with_output("wlr-randr", stdout));
+
+        let monitors = Monitor::get_monitors(&Runner(runner.clone()));
+
+        assert_eq!(*runner.calls.borrow(), vec![("wlr-randr".to_string(), vec!["--json".to_string()])]);
+        assert_eq!(monitors.len(), 2);
+        assert_eq!(monitors[0].name, "DP-1");
+        assert_eq!(monitors[0].get_current_resolution().unwrap().width, 3840);
+        assert_eq!(monitors[0].position, Some(Position { x: 0, y: 0 }));
+        assert_eq!(monitors[0].scale, Some(1.5));
+        assert!(monitors[0].vrr);
+        assert!(!monitors[1].enabled);
+        assert_eq!(monitors[1].position, None);
+    }
+
+    #[test]
+    fn deserialize_refresh_number_or_string() {
+        let mode = |refresh: &str| {
+            serde_json::from_str::<Resolution>(&format!(
+                r#"{{"width": 1920, "height": 1080, "refresh": {}, "preferred": true, "current": true}}"#,
+                refresh
+            ))
+        };
+        assert_eq!(mode("59.951").unwrap().refresh, 59.951);
+        assert_eq!(mode(r#""59.951""#).unwrap().refresh, 59.951);
+        assert_eq!(mode(r#""60.000 Hz""#).unwrap().refresh, 60.0);
+        assert_eq!(mode("144").unwrap().refresh, 144.0);
+        assert!(mode(r#""fast""#).is_err());
+    }
+
+    #[test]
+    fn get_monitors_drops_zero_refresh_modes() {
+        let stdout = r#"[{"name": "HEADLESS-1", "enabled": true, "position": {"x": 0, "y": 0}, "scale": 1.0, "modes": [
+            {"width": 1920, "height": 1080, "refresh": 0.0, "preferred": true, "current": true},
+            {"width": 1280, "height": 720, "refresh": 60.0, "preferred": false, "current": false},
+            {"width": 800, "height": 600, "refresh": -1.0, "preferred": false, "current": false}
+        ]}]"#;
+        let runner = Runner(Rc::new(FakeRunner::with_output("wlr-randr", stdout)));
+        let mut monitor = Monitor::get_monitors(&runner).remove(0);
+
+        assert_eq!(monitor.modes.len(), 1);
+        assert_eq!(monitor.modes[0].refresh, 60.0);
+
+        // the remaining mode becomes current
+        monitor.ensure_current_resolution();
+        assert_eq!(monitor.get_current_resolution().map(|m| m.width), Some(1280));
+        assert_eq!(monitor.max_refresh_mode(), Some(0));
+        assert!(monitor.to_hyprland_config(false).contains("1280x720@60"), "{}", monitor.to_hyprland_config(false));
+    }
+
+    #[test]
+    fn emit_without_position() {
+        let mut monitor = test_monitors().remove(1);
+        monitor.enable();
+        monitor.position = None;
+
+        assert_eq!(monitor.to_hyprland_config(true), "monitor = Monitor 2, 1280x720@60, 0x0, 1.25");
+        assert!(monitor.to_hyprland_v2_config(true).contains("position = 0x0"));
+        assert!(monitor.to_sway_config().contains(" pos 0 0 "), "{}", monitor.to_sway_config());
+        assert!(monitor.to_niri_config().contains("position x=0 y=0"));
+    }
+
+    #[test]
+    fn zero_refresh_only_output() {
+        let stdout = r#"[{"name": "HEADLESS-1", "enabled": true, "position": {"x": 0, "y": 0}, "scale": 1.0, "modes": [
+            {"width": 1920, "height": 1080, "refresh": 0.0, "preferred": true, "current": true}
+        ]}]"#;
+        let runner = Runner(Rc::new(FakeRunner::with_output("wlr-randr", stdout)));
+        let mut monitor = Monitor::get_monitors(&runner).remove(0);
+        monitor.ensure_current_resolution();
+        assert!(monitor.modes.is_empty());
+
+        // the compositor keeps choosing the mode
+        assert_eq!(monitor.to_hyprland_config(true), "monitor = HEADLESS-1, preferred, 0x0, 1");
+        assert!(monitor.to_hyprland_v2_config(true).contains("mode = preferred"));
+        assert_eq!(monitor.to_sway_config(), "output HEADLESS-1 pos 0 0 scale 1 transform normal");
+        assert!(!monitor.to_niri_config().contains("mode"));
+
+        let canvas = Monitor::get_monitors_canvas(&vec![monitor], &Rect::new(0, 0, 100, 30), 50.0);
+        assert!(canvas.cell_width.is_finite() && canvas.cell_height.is_finite());
+    }
+
+    #[test]
+    fn monitor_detection_resolves() {
+        let stdout = r#"[{"name": "DP-9", "enabled": true, "position": {"x": 0, "y": 0}, "scale": 1.0, "modes": [
+            {"width": 1920, "height": 1080, "refresh": 60.0, "preferred": true, "current": true}
+        ]}]"#;
+        let (release, wait) = mpsc::channel::<()>();
+        let detection = MonitorDetection::spawn(move || {
+            wait.recv().ok();
+            Ok(stdout.to_string())
+        });
+
+        assert!(detection.try_finish().is_none());
+        release.send(()).unwrap();
+        let monitors = loop {
+            if let Some(monitors) = detection.try_finish() {
+                break monitors;
+            }
+            thread::sleep(std::time::Duration::from_millis(5));
+        };
+
+        assert_eq!(monitors.len(), 1);
+        assert_eq!(monitors[0].name, "DP-9");
+        assert_eq!(monitors[0].modes.len(), 1);
+    }
+
+    #[test]
+    fn toggle_preferred_mode() {
+        let mut monitor = test_monitors()[0].clone();
+        monitor.modes = [(1920, true), (1280, false)]
+            .into_iter()
+            .map(|(width, preferred)| Resolution { width, height: 1080, refresh: 60.0, preferred, current: false, interlaced: false, custom: false })
+            .collect();
+        monitor.set_current_resolution(1);
+
+        monitor.toggle_preferred_mode();
+        assert_eq!(monitor.get_current_resolution().map(|m| m.width), Some(1920));
+        monitor.toggle_preferred_mode();
+        assert_eq!(monitor.get_current_resolution().map(|m| m.width), Some(1280));
+        monitor.toggle_preferred_mode();
+        assert_eq!(monitor.get_current_resolution().map(|m| m.width), Some(1920));
+
+        // nothing to go back to once the preferred mode was picked in the list
+        monitor.mode_before_preferred = None;
+        monitor.toggle_preferred_mode();
+        assert_eq!(monitor.get_current_resolution().map(|m| m.width), Some(1920));
+    }
+
+    #[test]
+    fn refresh_cap() {
+        let mut monitor = test_monitors()[0].clone();
+        monitor.modes = [(144.0, true), (60.0, false), (120.0, false)]
+            .into_iter()
+            .map(|(refresh, preferred)| Resolution { width: 1920, height: 1080, refresh, preferred, current: false, interlaced: false, custom: false })
+            .collect();
+        assert_eq!(monitor.max_refresh_mode(), Some(0));
+
+        // uncapped: the preferred mode, even when a faster one exists
+        let mut uncapped = monitor.clone();
+        uncapped.modes[0].preferred = false;
+        uncapped.modes[1].preferred = true;
+        uncapped.ensure_current_resolution();
+        assert_eq!(uncapped.get_current_resolution().map(|m| m.refresh), Some(60.0));
+
+        monitor.refresh_cap = Some(60.0);
+        assert_eq!(monitor.max_refresh_mode(), Some(1));
+        monitor.ensure_current_resolution();
+        assert_eq!(monitor.get_current_resolution().map(|m| m.refresh), Some(60.0));
+
+        // too low for every mode: the preferred one
+        monitor.modes[1].current = false;
+        monitor.refresh_cap = Some(30.0);
+        monitor.ensure_current_resolution();
+        assert_eq!(monitor.get_current_resolution().map(|m| m.refresh), Some(144.0));
+    }
+
+    #[test]
+    fn get_monitors_with_invalid_output() {
+        let runner = Runner(Rc::new(FakeRunner::with_output("wlr-randr", "not json")));
+        assert!(Monitor::get_monitors(&runner).is_empty());
+    }
+
+    #[test]
+    fn identify_restores_focus() {
+        let runner = Rc::new(FakeRunner::with_output(
+            "hyprctl",
+            r#"[{"name": "Monitor 2", "focused": true}, {"name": "Monitor 1", "focused": false}]"#,
+        ));
+
+        Monitor::identify(&Runner(runner.clone()), &test_monitors());
+
+        let calls = runner.calls.borrow();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(
+            calls[1].1,
+            vec![
+                "--batch".to_string(),
+                "dispatch focusmonitor Monitor 1 ; notify -1 3000 0 1: Monitor 1 ; dispatch focusmonitor Monitor 2".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn disable_enable_restores_geometry() {
+        let mut monitor = test_monitors()[0].clone();
+        monitor.position = Some(Position { x: 1280, y: -200 });
+        monitor.scale = Some(1.5);
+
+        monitor.disable();
+        monitor.disable();
+        assert!(!monitor.enabled);
+        assert_eq!(monitor.to_hyprland_config(false), "monitor = Monitor 1, disabled");
+
+        monitor.position = None;
+        monitor.scale = None;
+        monitor.enable();
+        assert!(monitor.enabled);
+        assert_eq!(monitor.position, Some(Position { x: 1280, y: -200 }));
+        assert_eq!(monitor.scale, Some(1.5));
+        assert_eq!(monitor.saved_position, None);
+        assert_eq!(monitor.saved_scale, None);
+    }
+
+    #[test]
+    fn enable_without_geometry() {
+        let mut monitor = test_monitors()[1].clone();
+        monitor.position = None;
+        monitor.scale = None;
+
+        monitor.enable();
+
+        assert_eq!(monitor.position, Some(Position { x: 0, y: 0 }));
+        assert_eq!(monitor.scale, Some(1.0));
+    }
+
+    #[test]
+    fn hyprland_config_with_settings() {
+        let mut monitor = test_monitors()[0].clone();
+        assert_eq!(monitor.to_hyprland_config(false), "monitor = Monitor 1, 1920x1080@60, 0x0, 1, transform,0");
+
+        monitor.vrr = true;
+        monitor.bit_depth = Some(10);
+        monitor.mirror = Some("Monitor 2".to_string());
+        assert_eq!(
+            monitor.to_hyprland_config(false),
+            "monitor = Monitor 1, 1920x1080@60, 0x0, 1, transform,0, vrr,1, bitdepth,10, mirror,Monitor 2"
+        );
+    }
+
+    #[test]
+    fn hyprland_v2_config() {
+        let mut monitor = test_monitors()[0].clone();
+        monitor.position = Some(Position { x: 1920, y: -200 });
+        monitor.scale = Some(1.25);
+        monitor.transform = Some("90".to_string());
+        monitor.vrr = true;
+        monitor.bit_depth = Some(10);
+        assert_eq!(
+            monitor.to_hyprland_v2_config(false),
+            "monitorv2 {\n    output = Monitor 1\n    mode = 1920x1080@60\n    position = 1920x-200\n    scale = 1.25\n    transform = 1\n    vrr = 1\n    bitdepth = 10\n}"
+        );
+
+        let monitor = test_monitors()[0].clone();
+        assert_eq!(
+            monitor.to_hyprland_v2_config(true),
+            "monitorv2 {\n    output = Monitor 1\n    mode = 1920x1080@60\n    position = 0x0\n    scale = 1\n}"
+        );
+        assert_eq!(
+            test_monitors()[1].to_hyprland_v2_config(false),
+            "monitorv2 {\n    output = Monitor 2\n    disabled = true\n}"
+        );
+    }
+
+    #[test]
+    fn hyprland_config_mode_keyword() {
+        let mut monitor = test_monitors()[0].clone();
+        assert_eq!(monitor.to_hyprland_config(true), "monitor = Monitor 1, 1920x1080@60, 0x0, 1");
+
+        monitor.mode_keyword = Some(ModeKeyword::HighRes);
+        assert_eq!(monitor.to_hyprland_config(true), "monitor = Monitor 1, highres, 0x0, 1");
+        assert!(monitor.to_hyprland_v2_config(true).contains("    mode = highres\n"));
+
+        monitor.mode_keyword = Some(ModeKeyword::HighRr);
+        assert_eq!(monitor.to_hyprland_config(true), "monitor = Monitor 1, highrr, 0x0, 1");
+    }
+
+    #[test]
+    fn minimal_hyprland_config() {
+        let mut monitor = test_monitors()[0].clone();
+        assert_eq!(monitor.to_hyprland_config(false), "monitor = Monitor 1, 1920x1080@60, 0x0, 1, transform,0");
+        assert_eq!(monitor.to_hyprland_config(true), "monitor = Monitor 1, 1920x1080@60, 0x0, 1");
+
+        monitor.transform = Some("90".to_string());
+        assert_eq!(monitor.to_hyprland_config(true), "monitor = Monitor 1, 1920x1080@60, 0x0, 1, transform,1");
+    }
+
+    #[test]
+    fn place_relative_to_target() {
+        let monitors = test_monitors();
+        let target = &monitors[0];
+        let mut monitor = monitors[1].clone();
+
+        // 1280x720 at 125% -> 1024x576
+        monitor.place_relative(target, Relation::RightOf);
+        assert_eq!(monitor.position, Some(Position { x: 1920, y: 0 }));
+
+        monitor.place_relative(target, Relation::Below);
+        assert_eq!(monitor.position, Some(Position { x: 0, y: 1080 }));
+
+        monitor.place_relative(target, Relation::LeftOf);
+        assert_eq!(monitor.position, Some(Position { x: -1024, y: 0 }));
+
+        monitor.place_relative(target, Relation::Above);
+        assert_eq!(monitor.position, Some(Position { x: 0, y: -576 }));
+    }
+
+    #[test]
+    fn parse_hyprland_config_lines() {
+        assert_eq!(
+            Monitor::parse_hyprland_config("monitor = DP-1, 2560x1440@143.97, 1920x-200, 1.25, transform,1"),
+            Some(HyprlandMonitor {
+                name: "DP-1".to_string(),
+                enabled: true,
+                resolution: Some((2560, 1440, Some(143.97))),
+                position: Some(Position { x: 1920, y: -200 }),
+                scale: Some(1.25),
+                transform: Some("90".to_string()),
+            })
+        );
+        assert_eq!(
+            Monitor::parse_hyprland_config("monitor=HDMI-A-1,1920x1080,0x0,1 # main"),
+            Some(HyprlandMonitor {
+                name: "HDMI-A-1".to_string(),
+                enabled: true,
+                resolution: Some((1920, 1080, None)),
+                position: Some(Position { x: 0, y: 0 }),
+                scale: Some(1.0),
+                transform: None,
+            })
+        );
+        assert_eq!(
+            Monitor::parse_hyprland_config("monitor = eDP-1, preferred, auto, auto"),
+            Some(HyprlandMonitor {
+                name: "eDP-1".to_string(),
+                enabled: true,
+                resolution: None,
+                position: None,
+                scale: None,
+                transform: None,
+            })
+        );
+        assert_eq!(
+            Monitor::parse_hyprland_config("monitor = DP-2, disabled"),
+            Some(HyprlandMonitor {
+                name: "DP-2".to_string(),
+                enabled: false,
+                resolution: None,
+                position: None,
+                scale: None,
+                transform: None,
+            })
+        );
+        assert_eq!(Monitor::parse_hyprland_config("workspace = 1, monitor:DP-1"), None);
+        assert_eq!(Monitor::parse_hyprland_config("# monitor = DP-1, disabled"), None);
+    }
+
+    #[test]
+    fn apply_hyprland_config_line() {
+        let mut monitor = test_monitors()[1].clone();
+        let config = Monitor::parse_hyprland_config("monitor = Monitor 2, 1920x1080@60, -1920x0, 1, transform,3").unwrap();
+
+        monitor.apply_hyprland_config(&config);
+
+        assert!(monitor.enabled);
+        assert_eq!(monitor.get_current_resolution().unwrap().width, 1920);
+        assert_eq!(monitor.position, Some(Position { x: -1920, y: 0 }));
+        assert_eq!(monitor.scale, Some(1.0));
+        assert_eq!(monitor.transform, Some("270".to_string()));
+    }
+
+    #[test]
+    fn custom_mode_warning() {
+        let mut monitor = test_monitors()[0].clone();
+        let config = Monitor::parse_hyprland_config("monitor = Monitor 1, 2560x1440@75, 0x0, 1").unwrap();
+        monitor.apply_hyprland_config(&config);
+
+        let mode = monitor.get_current_resolution().unwrap();
+        assert_eq!((mode.width, mode.height, mode.refresh), (2560, 1440, 75.0));
+        assert!(monitor.to_hyprland_config(false).contains("2560x1440@75"));
+        assert_eq!(
+            Monitor::layout_warnings(std::slice::from_ref(&monitor)),
+            vec!["Monitor 1 uses 2560x1440@75, not one of its modes"]
+        );
+
+        // back on an advertised mode
+        monitor.set_current_resolution(0);
+        assert_eq!(monitor.custom_mode_warning(), None);
+    }
+
+    #[test]
+    fn layout_warnings_for_stacked_monitors() {
+        let mut monitors = test_monitors();
+        monitors[1].enabled = true;
+        assert!(Monitor::layout_warnings(&monitors).is_empty());
+
+        monitors[1].position = Some(Position { x: 1000, y: 0 });
+        assert_eq!(Monitor::layout_warnings(&monitors), vec!["Monitor 1 overlaps Monitor 2"]);
+
+        monitors[1].position = Some(Position { x: 0, y: 0 });
+        assert_eq!(Monitor::layout_warnings(&monitors), vec!["Monitor 1 and Monitor 2 are stacked at (0,0)"]);
+
+        monitors[1].enabled = false;
+        assert!(Monitor::layout_warnings(&monitors).is_empty());
+    }
+
+    #[test]
+    fn mixed_scale_note() {
+        let mut monitors = test_monitors();
+        monitors[1].enabled = true;
+        monitors[1].scale = Some(1.0);
+        assert_eq!(Monitor::mixed_scale_note(&monitors), None);
+
+        monitors[1].scale = Some(2.0);
+        assert!(Monitor::mixed_scale_note(&monitors).is_some());
+
+        // only enabled monitors count
+        monitors[1].enabled = false;
+        assert_eq!(Monitor::mixed_scale_note(&monitors), None);
+    }
+
+    #[test]
+    fn canvas_far_from_origin() {
+        let mut monitors = test_monitors();
+        monitors[0].position = Some(Position { x: 20000, y: -15000 });
+
+        let canvas = Monitor::get_monitors_canvas(&monitors, &Rect::new(0, 0, 100, 30), 50.0);
+
+        assert_eq!(canvas.x_bounds, [19927.5, 21992.5]);
+        assert_eq!(canvas.y_bounds, [-15050.0, -13870.0]);
+    }
+
+    #[test]
+    fn canvas_margin() {
+        let monitors = test_monitors();
+        let area = Rect::new(0, 0, 100, 30);
+
+        let canvas = Monitor::get_monitors_canvas(&monitors, &area, 100.0);
+
+        // Monitor 1 alone, 1920x1080 at 0x0, padded sideways to its proportions
+        assert_eq!(canvas.x_bounds, [-160.0, 2080.0]);
+        assert_eq!(canvas.y_bounds, [-100.0, 1180.0]);
+        let default = Monitor::get_monitors_canvas(&monitors, &area, 50.0);
+        assert!(canvas.cell_width > default.cell_width);
+    }
+
+    #[test]
+    fn canvas_without_enabled_monitors() {
+        let mut monitors = test_monitors();
+        for monitor in &mut monitors {
+            monitor.enabled = false;
+        }
+
+        let canvas = Monitor::get_monitors_canvas(&monitors, &Rect::new(0, 0, 100, 30), 50.0);
+
+        assert_eq!(canvas.x_bounds, [-87.5, 87.5]);
+        assert_eq!(canvas.y_bounds, [-50.0, 50.0]);
+        assert!(canvas.cell_width.is_finite());
+    }
+
+    #[test]
+    fn save_config_workspace_lines() -> std::io::Result<()> {
+        let mut monitors = test_monitors();
+        monitors[0].default_workspace = Some("1".to_string());
+        let path = std::env::temp_dir().join("display-tui-workspace-test.conf");
+        let path = path.to_str().unwrap();
+
+        Monitor::save_config(path, &monitors, ConfigFormat::Hyprland, true)?;
+        let content = std::fs::read_to_string(path)?;
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines, vec![
+            "monitor = Monitor 1, 1920x1080@60, 0x0, 1",
+            "workspace = 1, monitor:Monitor 1",
+            "monitor = Monitor 2, disabled",
+        ]);
+
+        // only Hyprland has workspace rules
+        Monitor::save_config(path, &monitors, ConfigFormat::Sway, false)?;
+        assert!(!std::fs::read_to_string(path)?.contains("workspace"));
+        Ok(())
+    }
+
+    #[test]
+    fn save_split_config_sources_monitor_files() -> std::io::Result<()> {
+        let mut monitors = test_monitors();
+        monitors[0].default_workspace = Some("1".to_string());
+        let dir = std::env::temp_dir().join("display-tui-split-test");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join("monitors.conf");
+        let path = path.to_str().unwrap();
+
+        Monitor::save_split_config(path, &monitors, ConfigFormat::Hyprland, true)?;
+        let first = dir.join("monitors").join("Monitor 1.conf");
+        let second = dir.join("monitors").join("Monitor 2.conf");
+        assert_eq!(
+            std::fs::read_to_string(&first)?,
+            "monitor = Monitor 1, 1920x1080@60, 0x0, 1\nworkspace = 1, monitor:Monitor 1\n"
+        );
+        assert_eq!(std::fs::read_to_string(&second)?, "monitor = Monitor 2, disabled\n");
+        assert_eq!(
+            std::fs::read_to_string(path)?,
+            format!("source = {}\nsource = {}\n", first.display(), second.display())
+        );
+
+        // read back through the sources
+        let loaded = Monitor::load_hyprland_config(path).expect("No config");
+        assert_eq!(loaded.iter().map(|m| m.name.as_str()).collect::<Vec<_>>(), vec!["Monitor 1", "Monitor 2"]);
+        Ok(())
+    }
+
+    #[test]
+    fn bandwidth_warning_at_bit_depth() {
+        let mut monitor = test_monitors()[0].clone();
+        monitor.modes = vec![
+            Resolution { width: 3840, height: 2160, refresh: 144.0, preferred: false, current: true, interlaced: false, custom: false },
+            Resolution { width: 3840, height: 2160, refresh: 60.0, preferred: true, current: false, interlaced: false, custom: false },
+        ];
+        monitor.bit_depth = Some(10);
+
+        let warning = monitor.bandwidth_warning().expect("4K@144 10-bit is over the link limit");
+        assert!(warning.starts_with("Monitor 1 3840x2160@144 at 10-bit needs 35.8 Gbit/s"), "{}", warning);
+        assert_eq!(Monitor::layout_warnings(std::slice::from_ref(&monitor)), vec![warning]);
+
+        monitor.set_current_resolution(1);
+        monitor.bit_depth = Some(8);
+        assert_eq!(monitor.bandwidth_warning(), None);
+
+        // not checked without a chosen bit depth
+        monitor.set_current_resolution(0);
+        monitor.bit_depth = None;
+        assert_eq!(monitor.bandwidth_warning(), None);
+    }
+
+    #[test]
+    fn close_and_open_gap() {
+        let mut monitors = test_monitors();
+        monitors[1].enable();
+        let mut third = monitors[0].clone();
+        third.name = "Monitor 3".to_string();
+        third.position = Some(Position { x: 1920 + 1024, y: 0 });
+        monitors.push(third);
+
+        // Monitor 2 is 1280x720 at 1.25, so 1024 wide
+        Monitor::close_gap(&mut monitors, 1);
+        monitors[1].disable();
+        assert_eq!(monitors[2].position, Some(Position { x: 1920, y: 0 }));
+        assert_eq!(monitors[0].position, Some(Position { x: 0, y: 0 }));
+
+        monitors[1].enable();
+        Monitor::open_gap(&mut monitors, 1);
+        assert_eq!(monitors[2].position, Some(Position { x: 2944, y: 0 }));
+
+        // a locked monitor stays put
+        monitors[2].locked = true;
+        Monitor::close_gap(&mut monitors, 1);
+        assert_eq!(monitors[2].position, Some(Position { x: 2944, y: 0 }));
+    }
+
+    #[test]
+    fn align_edges() {
+        let mut monitors = test_monitors();
+        monitors[1].enable();
+        // 1920x1080, 1280x720 at 1.25 (1024x576) and 2560x1440
+        let mut third = monitors[0].clone();
+        third.name = "Monitor 3".to_string();
+        third.modes[0].width = 2560;
+        third.modes[0].height = 1440;
+        third.position = Some(Position { x: 2944, y: -300 });
+        monitors.push(third);
+        monitors[0].position = Some(Position { x: 0, y: 100 });
+        monitors[1].position = Some(Position { x: 1920, y: 50 });
+
+        let ys = |monitors: &[Monitor]| monitors.iter().map(|m| m.position.clone().unwrap().y).collect::<Vec<_>>();
+
+        Monitor::align_edges(&mut monitors, 0, false);
+        assert_eq!(ys(&monitors), vec![100, 100, 100]);
+
+        // bottoms at 100 + 1080
+        Monitor::align_edges(&mut monitors, 0, true);
+        assert_eq!(ys(&monitors), vec![100, 604, -260]);
+    }
+
+    #[test]
+    fn replace_hyprland_config_line() {
+        let mut monitors = test_monitors();
+        monitors[1].enable();
+        let content = "# monitors\n\
+            monitor = Monitor 1, 2560x1440@144, 0x0, 1\n\
+            monitor = Monitor 2, disabled # TV\n\
+            workspace = 1, monitor:Monitor 2\n";
+
+        assert_eq!(
+            monitors[1].replace_hyprland_config_line(content, true),
+            "# monitors\n\
+             monitor = Monitor 1, 2560x1440@144, 0x0, 1\n\
+             monitor = Monitor 2, 1280x720@60, 1920x0, 1.25 # TV\n\
+             workspace = 1, monitor:Monitor 2\n"
+        );
 
-        (x, y, logical_width, logical_height)
+        // no line for it yet
+        assert_eq!(
+            monitors[0].replace_hyprland_config_line("# monitors\n", true),
+            "# monitors\nmonitor = Monitor 1, 1920x1080@60, 0x0, 1\n"
+        );
     }
 }
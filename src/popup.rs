@@ -0,0 +1,129 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Style, Stylize, Color},
+    symbols::border,
+    text::Line,
+    widgets::{Block, Clear, Paragraph, Widget},
+};
+
+const CLOSE_HINT: &str = " Close <any key> ";
+const SCROLL_HINT: &str = " Scroll <j/k>  Close <any key> ";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Popup {
+    pub title: String,
+    pub lines: Vec<String>,
+    pub hint: String,
+    // j/k scroll the lines instead of closing the popup
+    pub scrollable: bool,
+    pub scroll: usize,
+}
+
+impl Popup {
+    pub fn new(title: &str, lines: Vec<String>) -> Self {
+        Popup {
+            title: title.to_string(),
+            lines,
+            hint: CLOSE_HINT.to_string(),
+            scrollable: false,
+            scroll: 0,
+        }
+    }
+
+    pub fn with_scroll(mut self) -> Self {
+        self.scrollable = true;
+        self.hint = SCROLL_HINT.to_string();
+        self
+    }
+
+    // Keeps at least the last line in view.
+    pub fn scroll_by(&mut self, delta: isize) {
+        let last = self.lines.len().saturating_sub(1);
+        self.scroll = self.scroll.saturating_add_signed(delta).min(last);
+    }
+
+    pub fn with_hint(mut self, hint: &str) -> Self {
+        self.hint = hint.to_string();
+        self
+    }
+
+    // Centered rect fitting the content, clamped to the available area
+    fn area(&self, area: Rect) -> Rect {
+        let content_width = self.lines.iter()
+            .map(|l| l.chars().count() + 1)
+            .chain([self.title.chars().count() + 2, self.hint.chars().count()])
+            .max()
+            .unwrap_or(0) as u16;
+        let width = (content_width + 4).min(area.width);
+        let height = (self.lines.len() as u16 + 2).min(area.height);
+
+        let [area] = Layout::horizontal([Constraint::Length(width)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [area] = Layout::vertical([Constraint::Length(height)])
+            .flex(Flex::Center)
+            .areas(area);
+        area
+    }
+}
+
+impl Widget for &Popup {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = self.area(area);
+        let title = Line::from(format!(" {} ", self.title).white().bold());
+        let block = Block::bordered()
+            .title(title.centered())
+            .title_bottom(Line::from(self.hint.as_str().white()).centered())
+            .border_set(border::THICK)
+            .border_style(Style::default().fg(Color::Yellow));
+
+        let lines: Vec<Line> = self.lines.iter()
+            .map(|l| Line::from(format!(" {}", l)))
+            .collect();
+
+        Clear.render(area, buf);
+        Paragraph::new(lines)
+            .block(block)
+            .scroll((self.scroll as u16, 0))
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::tests::row_text;
+
+    #[test]
+    fn render_popup() {
+        let popup = Popup::new("Changes", vec!["DP-1: enabled".to_string()]);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 30, 5));
+
+        (&popup).render(buf.area, &mut buf);
+
+        let lines: Vec<String> = (0..buf.area.height).map(|y| row_text(&buf, y)).collect();
+        assert_eq!(lines[0], "                              ");
+        assert_eq!(lines[1], "     ┏━━━━━ Changes ━━━━━┓    ");
+        assert_eq!(lines[2], "     ┃ DP-1: enabled     ┃    ");
+        assert_eq!(lines[3], "     ┗━ Close <any key> ━┛    ");
+    }
+
+    #[test]
+    fn scroll_popup() {
+        let lines = (1..=6).map(|i| format!("issue {}", i)).collect();
+        let mut popup = Popup::new("Validate", lines).with_scroll();
+        let mut buf = Buffer::empty(Rect::new(0, 0, 40, 5));
+
+        popup.scroll_by(2);
+        (&popup).render(buf.area, &mut buf);
+        let row = |y: u16| row_text(&buf, y);
+        assert!(row(1).contains("issue 3"), "{}", row(1));
+        assert!(row(4).contains("Scroll <j/k>"), "{}", row(4));
+
+        popup.scroll_by(10);
+        assert_eq!(popup.scroll, 5);
+        popup.scroll_by(-10);
+        assert_eq!(popup.scroll, 0);
+    }
+}
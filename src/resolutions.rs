@@ -9,14 +9,18 @@ use ratatui::{
 };
 
 use ratatui::layout::Constraint;
-use crate::monitor::Monitor;
-use crate::utils::TUIMode;
+use crate::monitor::{Monitor, Resolution};
+use crate::popup::Popup;
+use crate::utils::{self,TUIMode};
 use crate::App;
 
 #[derive(Debug)]
 pub struct Resolutions<'a> {
     pub state: TableState,
     pub monitor:&'a Monitor,
+    pub round_refresh: bool,
+    // `pinned_modes` from the config, listed first
+    pub pinned: &'a [String],
 }
 
 impl<'a> Resolutions<'a> {
@@ -26,14 +30,37 @@ impl<'a> Resolutions<'a> {
             state: TableState::default()
                 .with_selected(selected),
             monitor,
+            round_refresh: false,
+            pinned: &[],
         }
     }
+
+    pub fn with_round_refresh(mut self, round_refresh: bool) -> Self {
+        self.round_refresh = round_refresh;
+        self
+    }
+
+    pub fn with_pinned(mut self, pinned: &'a [String]) -> Self {
+        self.pinned = pinned;
+        self
+    }
  
     pub fn handle_events(app:&mut App, key_event: KeyEvent) {
         match key_event.code {
             KeyCode::Char('k') | KeyCode::Up => Resolutions::previous(app),
             KeyCode::Char('j') | KeyCode::Down => Resolutions::next(app),
+            KeyCode::PageUp => app.selected_resolution = utils::page_up(app.selected_resolution),
+            KeyCode::PageDown => {
+                let len = app.monitors[app.selected_monitor].modes.len();
+                app.selected_resolution = utils::page_down(app.selected_resolution, len);
+            },
+            KeyCode::Home => app.selected_resolution = 0,
+            KeyCode::End => {
+                app.selected_resolution = app.monitors[app.selected_monitor].modes.len().saturating_sub(1);
+            },
             KeyCode::Char(' ')=> Resolutions::select(app),
+            KeyCode::Char('M') => Resolutions::select_max_refresh(app),
+            KeyCode::Char('p') => Resolutions::toggle_pin(app),
             KeyCode::Esc => Resolutions::change_mode(app,TUIMode::View),
             _ => {}
         }
@@ -43,72 +70,172 @@ impl<'a> Resolutions<'a> {
     }
 
     fn next(app:&mut App) {
-        app.selected_resolution = if app.selected_resolution >= app.monitors[app.selected_monitor].modes.len() - 1 {
-            0
-        } else {
-            app.selected_resolution + 1
-        }
+        let len = app.monitors[app.selected_monitor].modes.len();
+        app.selected_resolution = utils::next_index(app.selected_resolution, len, app.config.wrap_navigation);
     }
 
     fn previous(app:&mut App) {
-        app.selected_resolution = if app.selected_resolution == 0 {
-            app.monitors[app.selected_monitor].modes.len() - 1
+        let len = app.monitors[app.selected_monitor].modes.len();
+        app.selected_resolution = utils::previous_index(app.selected_resolution, len, app.config.wrap_navigation);
+    }
+
+    // `selected_resolution` is a row in the sorted table, map it back to `modes`.
+    fn select(app:&mut App) {
+        let monitor = &mut app.monitors[app.selected_monitor];
+        if monitor.locked {
+            return;
+        }
+        if let Some(&index) = Resolutions::display_order(monitor, &app.config.pinned_modes).get(app.selected_resolution) {
+            monitor.set_current_resolution(index);
+        }
+    }
+
+    // Pins or unpins the highlighted mode, for every monitor, and keeps it
+    // highlighted at its new row.
+    fn toggle_pin(app:&mut App) {
+        let monitor = &app.monitors[app.selected_monitor];
+        let Some(&index) = Resolutions::display_order(monitor, &app.config.pinned_modes).get(app.selected_resolution) else { return };
+        let mode = &monitor.modes[index];
+        let mut config = app.config.clone();
+        if Resolutions::is_pinned(mode, &config.pinned_modes) {
+            config.pinned_modes.retain(|pin| !Resolutions::is_pinned(mode, std::slice::from_ref(pin)));
         } else {
-            app.selected_resolution - 1
+            config.pinned_modes.push(Resolutions::pin_label(mode));
+        }
+        if let Err(e) = config.save() {
+            app.popup = Some(Popup::new("Config not saved", vec![e]));
         }
+        app.selected_resolution = Resolutions::display_order(monitor, &config.pinned_modes)
+            .iter()
+            .position(|&i| i == index)
+            .unwrap_or(0);
+        app.config = config;
     }
 
-    fn select(app:&mut App) {
-        app.monitors[app.selected_monitor].set_current_resolution(app.selected_resolution);
+    pub fn pin_label(mode: &Resolution) -> String {
+        format!("{}x{}@{}", mode.width, mode.height, mode.refresh)
+    }
+
+    // A pin written by hand without decimals, `2560x1440@60`, also takes
+    // the 59.95Hz modes.
+    pub fn is_pinned(mode: &Resolution, pinned: &[String]) -> bool {
+        pinned.iter().any(|pin| {
+            let Some((size, refresh)) = pin.split_once('@') else { return false };
+            let Some((width, height)) = size.split_once('x') else { return false };
+            let Ok(refresh) = refresh.trim().parse::<f32>() else { return false };
+            width.trim().parse() == Ok(mode.width)
+                && height.trim().parse() == Ok(mode.height)
+                && (mode.refresh == refresh || (refresh.fract() == 0.0 && mode.refresh.round() == refresh))
+        })
+    }
+
+    // Rows of the table: the pinned modes, then the others, both sorted.
+    pub fn display_order(monitor: &Monitor, pinned: &[String]) -> Vec<usize> {
+        let (mut first, rest): (Vec<usize>, Vec<usize>) = monitor.sorted_mode_indices()
+            .into_iter()
+            .partition(|&index| Resolutions::is_pinned(&monitor.modes[index], pinned));
+        first.extend(rest);
+        first
+    }
+
+    fn select_max_refresh(app:&mut App) {
+        let monitor = &mut app.monitors[app.selected_monitor];
+        if monitor.locked {
+            return;
+        }
+        if let Some(index) = monitor.max_refresh_mode() {
+            monitor.set_current_resolution(index);
+        }
+    }
+
+    // Native size, plus the desktop space it gives at `scale` when scaled.
+    pub fn mode_label(mode: &Resolution, scale: f32) -> String {
+        let label = format!("{}x{}{}", mode.width, mode.height, if mode.interlaced { "i" } else { "" });
+        if scale == 1.0 {
+            return label;
+        }
+        let logical_width = (mode.width as f64 / scale as f64).round();
+        let logical_height = (mode.height as f64 / scale as f64).round();
+        format!("{} (logical {}x{} @ {})", label, logical_width, logical_height, scale)
+    }
+
+    // Width:height reduced by their gcd, snapped to the name the ratio is
+    // sold under when close to it: 2560x1080 is really 64:27 and 1920x1200 8:5.
+    pub fn aspect_label(width: i32, height: i32) -> String {
+        const NAMED: [(i32, i32); 7] = [(4, 3), (5, 4), (3, 2), (16, 10), (16, 9), (21, 9), (32, 9)];
+        const TOLERANCE: f64 = 0.03;
+
+        fn gcd(a: i32, b: i32) -> i32 {
+            if b == 0 { a } else { gcd(b, a % b) }
+        }
+
+        if width <= 0 || height <= 0 {
+            return String::new();
+        }
+        let ratio = width as f64 / height as f64;
+        let named = NAMED.iter()
+            .map(|&(w, h)| ((w, h), (ratio / (w as f64 / h as f64) - 1.0).abs()))
+            .filter(|&(_, error)| error <= TOLERANCE)
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        let (w, h) = match named {
+            Some((named, _)) => named,
+            None => {
+                let divisor = gcd(width, height);
+                (width / divisor, height / divisor)
+            }
+        };
+        format!("{}:{}", w, h)
+    }
+
+    // The precise value stays in the config, `~` marks a rounded one.
+    pub fn refresh_label(refresh: f32, round: bool) -> String {
+        if round && refresh.fract() != 0.0 {
+            format!("~{}", refresh.round())
+        } else {
+            refresh.to_string()
+        }
     }
 
     fn resolutions_to_rows(&self) -> Vec<Row<'static>> {
-        self.monitor.modes.clone()
+        Resolutions::display_order(self.monitor, self.pinned)
             .into_iter()
-            .map(|mode| {
+            .map(|index| {
+                let mode = &self.monitor.modes[index];
+                let pin = if Resolutions::is_pinned(mode, self.pinned) { "⚑ " } else { "" };
                 Row::new(vec![
                     Cell::default().content(
                         Line::from(
-                            if mode.current{
-                                "".green().to_string()
-                            } else {
-                                "".red().to_string()
-                            }
+                            if mode.current {"●"} else {" "}
                         )
                         .centered()
                     )
-                    .style(
-                        Style::default().fg(
-                            if mode.current {Color::Green} else {Color::Red}
+                    .style(Style::default().fg(Color::Green)),
+                    Cell::default().content(
+                        Line::from(
+                            format!("{}{}", pin, Resolutions::mode_label(mode, self.monitor.scale.unwrap_or(1.0)))
                         )
+                        .centered()
                     ),
                     Cell::default().content(
                         Line::from(
-                            format!("{}x{}",mode.width, mode.height)
+                            Resolutions::aspect_label(mode.width, mode.height)
                         )
                         .centered()
                     ),
                     Cell::default().content(
                         Line::from(
-                            mode.refresh.to_string()
+                            Resolutions::refresh_label(mode.refresh, self.round_refresh)
                         )
                         .centered()
                     ),
                     Cell::default().content(
                         Line::from(
-                            if mode.preferred {
-                                "".green().to_string()
-                            } else {
-                                "".red().to_string()
-                            }
+                            if mode.preferred {"★"} else {" "}
                         )
                         .centered()
                     )
-                    .style(
-                        Style::default().fg(
-                            if mode.preferred {Color::Green} else {Color::Red}
-                    )
-                )])
+                    .style(Style::default().fg(Color::Green)),
+                ])
             })
             .collect()
     }
@@ -123,10 +250,11 @@ impl<'a> Resolutions<'a> {
 
         let widths = [
             
-            Constraint::Percentage(25),
-            Constraint::Percentage(25),
-            Constraint::Percentage(25),
-            Constraint::Percentage(25),
+            Constraint::Percentage(12),
+            Constraint::Percentage(40),
+            Constraint::Percentage(12),
+            Constraint::Percentage(18),
+            Constraint::Percentage(18),
         ];   
 
         let table = Table::new(self.resolutions_to_rows(),widths) 
@@ -142,6 +270,10 @@ impl<'a> Resolutions<'a> {
                         Line::from("resolution")
                             .centered()
                     ),
+                    Cell::from(
+                        Line::from("aspect")
+                            .centered()
+                    ),
                     Cell::from(
                         Line::from("refresh")
                             .centered()
@@ -176,7 +308,9 @@ impl<'a> Resolutions<'a> {
 mod tests {
     use super::*;
     use ratatui::style::Style;
-    use crate::test_utils::tests::test_monitors;
+    use std::rc::Rc;
+    use crate::runner::Runner;
+    use crate::test_utils::tests::{row_text, test_monitors, FakeRunner};
 
     #[test]
     fn render_resolutions() {
@@ -184,7 +318,8 @@ mod tests {
         let mut resolutions = Resolutions {
             state: TableState::default(),
             monitor:&test_monitors()[0],
-            
+            round_refresh: false,
+            pinned: &[],
         }; 
         let mut buf = Buffer::empty(Rect::new(0, 0, 65, 7));
         
@@ -192,10 +327,10 @@ mod tests {
 
         let mut expected = Buffer::with_lines(vec![
             "┏━━━━━━━━━━━━━━━━━━━━━━━━━ Resolutions ━━━━━━━━━━━━━━━━━━━━━━━━━┓",
-            "┃    current       resolution        refresh        preferred   ┃",
+            "┃current       resolution       aspect    refresh     preferred ┃",
             "┃                                                               ┃",
-            "┃                  1920x1080          60                      ┃",
-            "┃                  1280x720           60                      ┃",
+            "┃   ●           1920x1080        16:9        60           ★     ┃",
+            "┃               1280x720         16:9        60                 ┃",
             "┃                                                               ┃",
             "┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛",
         ]);
@@ -204,7 +339,6 @@ mod tests {
         let header_style = Style::new().green().bold().reversed();
         let empty_style = Style::new();
         let ok_style = Style::new().fg(Color::Green);
-        let nok_style = Style::new().fg(Color::Red);
         let row_style = Style::new();
 
         // first line : title
@@ -224,16 +358,16 @@ mod tests {
          
         // fourth line : first row 
         expected.set_style(Rect::new(0, 3, 1, 1), border_style);
-        expected.set_style(Rect::new(1, 3, 15, 1), ok_style);
-        expected.set_style(Rect::new(16, 3, 33, 1), row_style);
-        expected.set_style(Rect::new(49, 3, 15, 1), ok_style);
+        expected.set_style(Rect::new(1, 3, 8, 1), ok_style);
+        expected.set_style(Rect::new(9, 3, 44, 1), row_style);
+        expected.set_style(Rect::new(53, 3, 11, 1), ok_style);
         expected.set_style(Rect::new(64, 3, 1, 1), border_style);      
 
         // fifth line : second row 
         expected.set_style(Rect::new(0, 4, 1, 1), border_style);
-        expected.set_style(Rect::new(1, 4, 15, 1), nok_style);
-        expected.set_style(Rect::new(16, 4, 33, 1), row_style);
-        expected.set_style(Rect::new(49, 4, 15, 1), nok_style);
+        expected.set_style(Rect::new(1, 4, 8, 1), ok_style);
+        expected.set_style(Rect::new(9, 4, 44, 1), row_style);
+        expected.set_style(Rect::new(53, 4, 11, 1), ok_style);
         expected.set_style(Rect::new(64, 4, 1, 1), border_style);  
         
         // fifth line : empty
@@ -246,4 +380,119 @@ mod tests {
 
         assert_eq!(buf, expected);
     }
+
+    #[test]
+    fn render_resolutions_scrolls_to_selection() {
+        let mut monitor = test_monitors()[0].clone();
+        monitor.modes = (0..10)
+            .map(|i| Resolution {
+                width: 1009 - i,
+                height: 1000,
+                refresh: 60.0,
+                preferred: i == 0,
+                current: i == 8,
+                interlaced: false,
+                custom: false,
+            })
+            .collect();
+
+        let mut resolutions = Resolutions::new(&monitor, Some(8));
+        let mut buf = Buffer::empty(Rect::new(0, 0, 65, 7));
+
+        resolutions.render(buf.area, &mut buf);
+
+        // three visible rows below the header, scrolled so the selection is the last one
+        let rows: Vec<String> = (3..6).map(|y| row_text(&buf, y)).collect();
+        assert!(rows[0].contains("1003x1000"), "{}", rows[0]);
+        assert!(rows[2].contains("1001x1000"), "{}", rows[2]);
+        assert!(rows[2].contains("●"), "{}", rows[2]);
+        assert!(!rows[1].contains("●"), "{}", rows[1]);
+    }
+
+    #[test]
+    fn render_resolutions_sorted() {
+        let mut monitor = test_monitors()[0].clone();
+        monitor.modes = [(1280, 720, 60.0), (2560, 1440, 60.0), (1920, 1080, 60.0), (2560, 1440, 144.0), (1920, 1200, 60.0)]
+            .into_iter()
+            .map(|(width, height, refresh)| Resolution { width, height, refresh, preferred: false, current: false, interlaced: false, custom: false })
+            .collect();
+
+        assert_eq!(monitor.sorted_mode_indices(), vec![3, 1, 4, 2, 0]);
+
+        let mut resolutions = Resolutions::new(&monitor, None);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 65, 10));
+
+        resolutions.render(buf.area, &mut buf);
+
+        let rows: Vec<String> = (3..8).map(|y| row_text(&buf, y)).collect();
+        let expected = ["2560x1440 16:9 144", "2560x1440 16:9 60", "1920x1200 16:10 60", "1920x1080 16:9 60", "1280x720 16:9 60"];
+        for (row, expected) in rows.iter().zip(expected) {
+            let row = row.trim_matches('┃').split_whitespace().collect::<Vec<_>>().join(" ");
+            assert_eq!(row, expected);
+        }
+    }
+
+    #[test]
+    fn render_resolutions_pinned_first() {
+        let mut monitor = test_monitors()[0].clone();
+        monitor.modes = [(2560, 1440, 144.0), (1920, 1080, 59.94), (1280, 720, 60.0)]
+            .into_iter()
+            .map(|(width, height, refresh)| Resolution { width, height, refresh, preferred: false, current: false, interlaced: false, custom: false })
+            .collect();
+        let pinned = vec!["1920x1080@60".to_string()];
+
+        assert_eq!(Resolutions::display_order(&monitor, &pinned), vec![1, 0, 2]);
+        assert_eq!(Resolutions::display_order(&monitor, &[]), vec![0, 1, 2]);
+
+        let mut resolutions = Resolutions::new(&monitor, None).with_pinned(&pinned);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 65, 8));
+        resolutions.render(buf.area, &mut buf);
+
+        let row = |y: u16| row_text(&buf, y);
+        assert!(row(3).contains("⚑ 1920x1080"), "{}", row(3));
+        assert!(row(4).contains("2560x1440") && !row(4).contains("⚑"), "{}", row(4));
+    }
+
+    #[test]
+    fn mode_label_with_logical_size() {
+        let monitor = &test_monitors()[0];
+        assert_eq!(Resolutions::mode_label(&monitor.modes[0], 1.0), "1920x1080");
+        assert_eq!(Resolutions::mode_label(&monitor.modes[0], 1.25), "1920x1080 (logical 1536x864 @ 1.25)");
+        assert_eq!(Resolutions::mode_label(&monitor.modes[1], 1.5), "1280x720 (logical 853x480 @ 1.5)");
+    }
+
+    #[test]
+    fn mode_label_interlaced() {
+        let stdout = r#"[{"name": "HDMI-A-1", "enabled": true, "position": {"x": 0, "y": 0}, "scale": 1.0, "modes": [
+            {"width": 1920, "height": 1080, "refresh": 60.0, "preferred": true, "current": true},
+            {"width": 1920, "height": 1080, "refresh": 60.0, "preferred": false, "current": false, "interlaced": true}
+        ]}]"#;
+        let runner = Runner(Rc::new(FakeRunner::with_output("wlr-randr", stdout)));
+        let monitor = &Monitor::get_monitors(&runner)[0];
+
+        assert!(!monitor.modes[0].interlaced);
+        assert!(monitor.modes[1].interlaced);
+        assert_eq!(Resolutions::mode_label(&monitor.modes[1], 1.0), "1920x1080i");
+        assert_eq!(monitor.sorted_mode_indices(), vec![0, 1]);
+        assert_eq!(monitor.find_mode(1920, 1080, 60.0), Some(0));
+    }
+
+    #[test]
+    fn aspect_label_common_ratios() {
+        assert_eq!(Resolutions::aspect_label(2560, 1080), "21:9");
+        assert_eq!(Resolutions::aspect_label(3440, 1440), "21:9");
+        assert_eq!(Resolutions::aspect_label(1920, 1200), "16:10");
+        assert_eq!(Resolutions::aspect_label(1920, 1080), "16:9");
+        assert_eq!(Resolutions::aspect_label(1366, 768), "16:9");
+        assert_eq!(Resolutions::aspect_label(1280, 1024), "5:4");
+        assert_eq!(Resolutions::aspect_label(1080, 1920), "9:16");
+    }
+
+    #[test]
+    fn refresh_label_rounded() {
+        assert_eq!(Resolutions::refresh_label(59.951, false), "59.951");
+        assert_eq!(Resolutions::refresh_label(59.951, true), "~60");
+        assert_eq!(Resolutions::refresh_label(143.5, true), "~144");
+        assert_eq!(Resolutions::refresh_label(60.0, true), "60");
+    }
 }
@@ -1,27 +1,40 @@
 use serde::Deserialize;
 
-#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[derive(Debug, Default, Clone, Deserialize, PartialEq)]
 pub enum Rotation {
+    #[default]
     Normal,
     Deg90,
     Deg180,
     Deg270,
+    // mirrored horizontally, then rotated
+    Flipped,
+    Flipped90,
+    Flipped180,
+    Flipped270,
 }
 
-impl Default for Rotation {
-    fn default() -> Self {
-        Rotation::Normal
-    }
-}
+// Every rotation, in the Hyprland transform order
+const ALL: [Rotation; 8] = [
+    Rotation::Normal,
+    Rotation::Deg90,
+    Rotation::Deg180,
+    Rotation::Deg270,
+    Rotation::Flipped,
+    Rotation::Flipped90,
+    Rotation::Flipped180,
+    Rotation::Flipped270,
+];
 
 impl Rotation {
+    // wlr-randr spellings, unknown or missing values are Normal.
     pub fn from_transform(transform: &Option<String>) -> Self {
-        match transform.as_deref() {
-            Some("90") => Rotation::Deg90,
-            Some("180") => Rotation::Deg180,
-            Some("270") => Rotation::Deg270,
-            _ => Rotation::Normal,
-        }
+        let Some(transform) = transform else { return Rotation::Normal };
+        let transform = transform.trim().to_lowercase();
+        ALL.iter()
+            .find(|rotation| rotation.to_transform() == transform)
+            .cloned()
+            .unwrap_or_default()
     }
 
     pub fn to_transform(&self) -> &str {
@@ -30,24 +43,81 @@ impl Rotation {
             Rotation::Deg90 => "90",
             Rotation::Deg180 => "180",
             Rotation::Deg270 => "270",
+            Rotation::Flipped => "flipped",
+            Rotation::Flipped90 => "flipped-90",
+            Rotation::Flipped180 => "flipped-180",
+            Rotation::Flipped270 => "flipped-270",
         }
     }
 
     pub fn to_hyprland(&self) -> i32 {
-        match self {
-            Rotation::Normal => 0,
-            Rotation::Deg90 => 1,
-            Rotation::Deg180 => 2,
-            Rotation::Deg270 => 3,
-        }
+        ALL.iter().position(|rotation| rotation == self).unwrap_or(0) as i32
+    }
+
+    pub fn from_hyprland(transform: i32) -> Self {
+        usize::try_from(transform).ok()
+            .and_then(|index| ALL.get(index))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    // Quarter turns swap the width and height of the output
+    pub fn is_portrait(&self) -> bool {
+        matches!(self, Rotation::Deg90 | Rotation::Deg270 | Rotation::Flipped90 | Rotation::Flipped270)
     }
 
+    // Next quarter turn, a flipped output stays flipped.
     pub fn cycle(&self) -> Self {
         match self {
             Rotation::Normal => Rotation::Deg90,
             Rotation::Deg90 => Rotation::Deg180,
             Rotation::Deg180 => Rotation::Deg270,
             Rotation::Deg270 => Rotation::Normal,
+            Rotation::Flipped => Rotation::Flipped90,
+            Rotation::Flipped90 => Rotation::Flipped180,
+            Rotation::Flipped180 => Rotation::Flipped270,
+            Rotation::Flipped270 => Rotation::Flipped,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transform_spellings() {
+        let cases = [
+            (Some("normal"), Rotation::Normal, 0),
+            (Some("90"), Rotation::Deg90, 1),
+            (Some("180"), Rotation::Deg180, 2),
+            (Some("270"), Rotation::Deg270, 3),
+            (Some("flipped"), Rotation::Flipped, 4),
+            (Some("flipped-90"), Rotation::Flipped90, 5),
+            (Some("flipped-180"), Rotation::Flipped180, 6),
+            (Some("flipped-270"), Rotation::Flipped270, 7),
+            (Some(" Flipped-90 "), Rotation::Flipped90, 5),
+            (Some("upside-down"), Rotation::Normal, 0),
+            (Some(""), Rotation::Normal, 0),
+            (None, Rotation::Normal, 0),
+        ];
+        for (transform, rotation, hyprland) in cases {
+            let parsed = Rotation::from_transform(&transform.map(str::to_string));
+            assert_eq!(parsed, rotation, "{:?}", transform);
+            assert_eq!(parsed.to_hyprland(), hyprland, "{:?}", transform);
+            // both spellings round-trip
+            assert_eq!(Rotation::from_hyprland(hyprland), rotation);
+            assert_eq!(Rotation::from_transform(&Some(rotation.to_transform().to_string())), rotation);
         }
+        assert_eq!(Rotation::from_hyprland(8), Rotation::Normal);
+        assert_eq!(Rotation::from_hyprland(-1), Rotation::Normal);
+    }
+
+    #[test]
+    fn cycle_keeps_flip() {
+        assert_eq!(Rotation::Deg270.cycle(), Rotation::Normal);
+        assert_eq!(Rotation::Flipped270.cycle(), Rotation::Flipped);
+        assert!(Rotation::Flipped90.is_portrait());
+        assert!(!Rotation::Flipped180.is_portrait());
     }
 }
@@ -0,0 +1,71 @@
+use std::fmt::Debug;
+use std::fs;
+use std::io;
+use std::process::Command;
+use std::rc::Rc;
+
+// Seam for everything that shells out (wlr-randr, hyprctl, ...)
+pub trait CommandRunner: Debug {
+    // Runs `program` with `args` and returns its stdout, or its stderr as
+    // the error when it exits with a failure status.
+    fn run(&self, program: &str, args: &[String]) -> io::Result<String>;
+}
+
+#[derive(Debug, Default)]
+pub struct SystemRunner;
+
+impl CommandRunner for SystemRunner {
+    fn run(&self, program: &str, args: &[String]) -> io::Result<String> {
+        let output = Command::new(program)
+            .args(args)
+            .output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let message = match stderr.trim() {
+                "" => output.status.to_string(),
+                stderr => stderr.to_string(),
+            };
+            return Err(io::Error::other(message));
+        }
+        String::from_utf8(output.stdout)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+// Answers wlr-randr with a saved `wlr-randr --json` output, for demos and
+// bug reports. Everything else still runs.
+#[derive(Debug)]
+pub struct RecordedRunner {
+    pub wlr_randr: String,
+    pub fallback: SystemRunner,
+}
+
+impl CommandRunner for RecordedRunner {
+    fn run(&self, program: &str, args: &[String]) -> io::Result<String> {
+        if program == "wlr-randr" {
+            return Ok(self.wlr_randr.clone());
+        }
+        self.fallback.run(program, args)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Runner(pub Rc<dyn CommandRunner>);
+
+impl Default for Runner {
+    fn default() -> Self {
+        Runner(Rc::new(SystemRunner))
+    }
+}
+
+impl Runner {
+    // Monitors read from the JSON file at `path` instead of wlr-randr.
+    pub fn with_monitors_from(path: &str) -> io::Result<Self> {
+        let wlr_randr = fs::read_to_string(shellexpand::tilde(path).as_ref())?;
+        Ok(Runner(Rc::new(RecordedRunner { wlr_randr, fallback: SystemRunner })))
+    }
+
+    pub fn run(&self, program: &str, args: &[String]) -> io::Result<String> {
+        self.0.run(program, args)
+    }
+}
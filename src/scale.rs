@@ -10,9 +10,8 @@ use ratatui::{
 
 use ratatui::layout::Constraint;
 use crate::utils::ScaleValue;
-use crate::utils::TUIMode;
+use crate::utils::{self,TUIMode};
 use crate::App;
-use crate::configuration::Configuration;
 
 #[derive(Debug)]
 pub struct Scale{
@@ -32,42 +31,81 @@ impl Scale{
         match key_event.code {
             KeyCode::Char('k') | KeyCode::Up => Scale::previous(app),
             KeyCode::Char('j') | KeyCode::Down => Scale::next(app),
+            KeyCode::PageUp => app.selected_scale = utils::page_up(app.selected_scale),
+            KeyCode::PageDown => app.selected_scale = utils::page_down(app.selected_scale, ScaleValue::table().len()),
+            KeyCode::Home => app.selected_scale = 0,
+            KeyCode::End => app.selected_scale = ScaleValue::table().len() - 1,
             KeyCode::Char(' ')=> Scale::select(app),
+            KeyCode::Char('+') => Scale::adjust(app, app.config.scale_step),
+            KeyCode::Char('-') => Scale::adjust(app, -app.config.scale_step),
             KeyCode::Esc => Scale::change_mode(app,TUIMode::View),
-            _ => {}
+            _ => return,
         }
+        Scale::update_preview(app, key_event);
+    }
+
+    // Moving through the table previews the hovered scale on the map,
+    // anything else shows the monitor's own scale again.
+    fn update_preview(app:&mut App, key_event: KeyEvent) {
+        let hovering = matches!(
+            key_event.code,
+            KeyCode::Char('k') | KeyCode::Up | KeyCode::Char('j') | KeyCode::Down
+                | KeyCode::PageUp | KeyCode::PageDown | KeyCode::Home | KeyCode::End
+        );
+        app.scale_preview = if hovering && !app.monitors[app.selected_monitor].locked {
+            Some(ScaleValue::table()[app.selected_scale].value)
+        } else {
+            None
+        };
     }
     fn change_mode(app:&mut App,mode: TUIMode) {
         // Save monitor state when exiting Scale mode
         if app.mode == TUIMode::Scale {
-            let _ = Configuration::save_monitor_state(&app.monitors);
+            let _ = app.config.save_monitor_state(&app.monitors);
         }
         app.mode = mode;
     }
 
     fn next(app:&mut App) {
-        app.selected_scale = if app.selected_scale >= ScaleValue::table().len() - 1 {
-            0
-        } else {
-            app.selected_scale + 1
-        }
+        app.selected_scale = utils::next_index(app.selected_scale, ScaleValue::table().len(), app.config.wrap_navigation);
     }
 
     fn previous(app:&mut App) {
-        app.selected_scale = if app.selected_scale == 0 {
-            ScaleValue::table().len() - 1
-        } else {
-            app.selected_scale - 1
-        }
+        app.selected_scale = utils::previous_index(app.selected_scale, ScaleValue::table().len(), app.config.wrap_navigation);
     }
 
     fn select(app:&mut App) {
+        if app.monitors[app.selected_monitor].locked {
+            return;
+        }
         let scale_value = Some(ScaleValue::table()[app.selected_scale].value);
         app.monitors[app.selected_monitor].scale = scale_value;
     }
 
 
 
+    // Table value after `scale`, back to the smallest past the largest.
+    pub fn next_preset(scale: f32) -> f32 {
+        let table = ScaleValue::table();
+        table.iter()
+            .map(|s| s.value)
+            .find(|&value| value > scale + 0.001)
+            .unwrap_or(table[0].value)
+    }
+
+    // Steps the scale outside of the table values, rounded to hundredths
+    // so repeated steps don't drift.
+    fn adjust(app:&mut App, step: f32) {
+        const MIN_SCALE: f32 = 0.25;
+        const MAX_SCALE: f32 = 4.0;
+        let monitor = &mut app.monitors[app.selected_monitor];
+        if monitor.locked {
+            return;
+        }
+        let scale = (monitor.scale.unwrap_or(1.0) + step).clamp(MIN_SCALE, MAX_SCALE);
+        monitor.scale = Some((scale * 100.0).round() / 100.0);
+    }
+
     fn scale_to_rows(&self) -> Vec<Row<'static>> {
         
         ScaleValue::table()
@@ -118,6 +156,19 @@ mod tests {
     use super::*;
     use ratatui::style::Style;
 
+    #[test]
+    fn next_preset_wraps() {
+        let mut scale = 1.0;
+        let mut seen = vec![];
+        for _ in 0..6 {
+            scale = Scale::next_preset(scale);
+            seen.push(scale);
+        }
+        assert_eq!(seen, vec![1.25, 1.6, 1.75, 2.0, 0.5, 0.6]);
+        // a scale set with +/- moves to the next value up
+        assert_eq!(Scale::next_preset(1.1), 1.25);
+    }
+
     #[test]
     fn render_scale() {
 
@@ -0,0 +1,268 @@
+use crossterm::event::{KeyCode,KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Style,Stylize,Color},
+    symbols::border,
+    text::Line,
+    widgets::{Block,StatefulWidget,Row,Table,Cell,TableState},
+};
+
+use ratatui::layout::Constraint;
+use crate::monitor::{ModeKeyword, Monitor};
+use crate::rotation::Rotation;
+use crate::utils::{self,TUIMode};
+use crate::App;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Setting {
+    Rotation,
+    Vrr,
+    BitDepth,
+    Primary,
+    Mirror,
+    Label,
+    Workspace,
+    RefreshCap,
+    ModeKeyword,
+}
+
+impl Setting {
+    pub fn table() -> Vec<Self> {
+        vec![
+            Setting::Rotation,
+            Setting::Vrr,
+            Setting::BitDepth,
+            Setting::Primary,
+            Setting::Mirror,
+            Setting::Label,
+            Setting::Workspace,
+            Setting::RefreshCap,
+            Setting::ModeKeyword,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Setting::Rotation => "rotation",
+            Setting::Vrr => "vrr",
+            Setting::BitDepth => "bit depth",
+            Setting::Primary => "primary",
+            Setting::Mirror => "mirror",
+            Setting::Label => "label",
+            Setting::Workspace => "workspace",
+            Setting::RefreshCap => "refresh cap",
+            Setting::ModeKeyword => "mode keyword",
+        }
+    }
+
+    pub fn value(&self, monitor: &Monitor) -> String {
+        match self {
+            Setting::Rotation => monitor.transform.clone().unwrap_or("normal".to_string()),
+            Setting::Vrr => if monitor.vrr { "on" } else { "off" }.to_string(),
+            Setting::BitDepth => monitor.bit_depth.unwrap_or(8).to_string(),
+            Setting::Primary => if monitor.primary { "yes" } else { "no" }.to_string(),
+            Setting::Mirror => monitor.mirror.clone().unwrap_or("none".to_string()),
+            Setting::Label => monitor.label.clone().unwrap_or("none".to_string()),
+            Setting::Workspace => monitor.default_workspace.clone().unwrap_or("none".to_string()),
+            Setting::RefreshCap => monitor.refresh_cap.map(|cap| format!("{}Hz", cap)).unwrap_or("none".to_string()),
+            Setting::ModeKeyword => monitor.mode_keyword.map(|k| k.to_hyprland()).unwrap_or("none").to_string(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Settings<'a> {
+    pub state: TableState,
+    pub monitor: &'a Monitor,
+    pub editing_text: Option<&'a str>,
+}
+
+impl<'a> Settings<'a> {
+    pub fn new(monitor: &'a Monitor, selected: usize, editing_text: Option<&'a str>) -> Self {
+        Settings {
+            state: TableState::default()
+                .with_selected(selected),
+            monitor,
+            editing_text,
+        }
+    }
+
+    pub fn handle_events(app:&mut App, key_event: KeyEvent) {
+        if app.editing_text.is_some() {
+            Settings::handle_text_events(app, key_event);
+            return;
+        }
+        match key_event.code {
+            KeyCode::Char('k') | KeyCode::Up => Settings::previous(app),
+            KeyCode::Char('j') | KeyCode::Down => Settings::next(app),
+            KeyCode::Char(' ') | KeyCode::Enter => Settings::toggle(app),
+            KeyCode::Esc => Settings::change_mode(app,TUIMode::View),
+            _ => {}
+        }
+    }
+
+    fn handle_text_events(app:&mut App, key_event: KeyEvent) {
+        let Some(text) = app.editing_text.as_mut() else { return };
+        match key_event.code {
+            KeyCode::Char(c) => text.push(c),
+            KeyCode::Backspace => { text.pop(); },
+            KeyCode::Enter => {
+                let text = app.editing_text.take().unwrap_or_default();
+                let value = if text.is_empty() { None } else { Some(text) };
+                let monitor = &mut app.monitors[app.selected_monitor];
+                match Setting::table()[app.selected_setting] {
+                    Setting::Workspace => monitor.default_workspace = value,
+                    _ => monitor.label = value,
+                }
+            },
+            KeyCode::Esc => app.editing_text = None,
+            _ => {}
+        }
+    }
+
+    fn change_mode(app:&mut App,mode: TUIMode) {
+        app.mode = mode;
+    }
+
+    fn next(app:&mut App) {
+        app.selected_setting = utils::next_index(app.selected_setting, Setting::table().len(), app.config.wrap_navigation);
+    }
+
+    fn previous(app:&mut App) {
+        app.selected_setting = utils::previous_index(app.selected_setting, Setting::table().len(), app.config.wrap_navigation);
+    }
+
+    fn toggle(app:&mut App) {
+        let selected = app.selected_monitor;
+        match Setting::table()[app.selected_setting] {
+            Setting::Rotation => {
+                let monitor = &mut app.monitors[selected];
+                let rotation = Rotation::from_transform(&monitor.transform).cycle();
+                monitor.transform = Some(rotation.to_transform().to_string());
+            },
+            Setting::Vrr => app.monitors[selected].vrr = !app.monitors[selected].vrr,
+            Setting::BitDepth => {
+                let monitor = &mut app.monitors[selected];
+                monitor.bit_depth = match monitor.bit_depth {
+                    Some(10) => None,
+                    _ => Some(10),
+                };
+            },
+            Setting::Primary => {
+                let primary = !app.monitors[selected].primary;
+                for (i, monitor) in app.monitors.iter_mut().enumerate() {
+                    monitor.primary = i == selected && primary;
+                }
+            },
+            Setting::Mirror => {
+                // cycle through the other monitors, then back to none
+                let others: Vec<String> = app.monitors.iter()
+                    .enumerate()
+                    .filter(|(i, _)| *i != selected)
+                    .map(|(_, m)| m.name.clone())
+                    .collect();
+                let monitor = &mut app.monitors[selected];
+                let next = match &monitor.mirror {
+                    None => others.first().cloned(),
+                    Some(current) => others.iter()
+                        .position(|name| name == current)
+                        .and_then(|i| others.get(i + 1).cloned()),
+                };
+                monitor.mirror = next;
+            },
+            Setting::Label => {
+                app.editing_text = Some(app.monitors[selected].label.clone().unwrap_or_default());
+            },
+            Setting::Workspace => {
+                app.editing_text = Some(app.monitors[selected].default_workspace.clone().unwrap_or_default());
+            },
+            Setting::RefreshCap => {
+                const CAPS: [f32; 4] = [60.0, 75.0, 120.0, 144.0];
+                let monitor = &mut app.monitors[selected];
+                monitor.refresh_cap = match monitor.refresh_cap {
+                    None => Some(CAPS[0]),
+                    Some(cap) => CAPS.iter().copied().find(|&c| c > cap),
+                };
+            },
+            Setting::ModeKeyword => {
+                let monitor = &mut app.monitors[selected];
+                monitor.mode_keyword = match monitor.mode_keyword {
+                    None => Some(ModeKeyword::HighRes),
+                    Some(ModeKeyword::HighRes) => Some(ModeKeyword::HighRr),
+                    Some(ModeKeyword::HighRr) => None,
+                };
+            },
+        }
+    }
+
+    fn settings_to_rows(&self) -> Vec<Row<'static>> {
+        Setting::table()
+            .into_iter()
+            .map(|setting| {
+                // the text being typed replaces the selected value
+                let editing = Setting::table().get(self.state.selected().unwrap_or(0)) == Some(&setting);
+                let value = match self.editing_text {
+                    Some(text) if editing => format!("{}_", text),
+                    _ => setting.value(self.monitor),
+                };
+                Row::new(vec![
+                    Cell::from(setting.name()),
+                    Cell::from(value),
+                ])
+            })
+            .collect()
+    }
+
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let title = Line::from(" Settings ".bold());
+        let block = Block::bordered()
+            .title(title.white().centered())
+            .border_set(border::THICK)
+            .border_style(Style::default().fg(Color::Yellow));
+
+        let widths = [
+            Constraint::Percentage(50),
+            Constraint::Percentage(50),
+        ];
+
+        let table = Table::new(self.settings_to_rows(),widths)
+            .column_spacing(1)
+            .row_highlight_style(Style::new().yellow())
+            .cell_highlight_style(Style::new().blue())
+            .highlight_symbol("  ")
+            .block(block);
+
+        StatefulWidget::render(
+            table,
+            area,
+            buf,
+            &mut self.state,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::tests::{row_text, test_monitors};
+
+    #[test]
+    fn render_settings() {
+        let monitors = test_monitors();
+        let mut settings = Settings::new(&monitors[0], 5, Some("desk"));
+        let mut buf = Buffer::empty(Rect::new(0, 0, 30, 9));
+
+        settings.render(buf.area, &mut buf);
+
+        let lines: Vec<String> = (0..buf.area.height).map(|y| row_text(&buf, y)).collect();
+        assert_eq!(lines[0], "┏━━━━━━━━━ Settings ━━━━━━━━━┓");
+        assert_eq!(lines[1], "┃  rotation      normal      ┃");
+        assert_eq!(lines[2], "┃  vrr           off         ┃");
+        assert_eq!(lines[3], "┃  bit depth     8           ┃");
+        assert_eq!(lines[4], "┃  primary       no          ┃");
+        assert_eq!(lines[5], "┃  mirror        none        ┃");
+        assert_eq!(lines[6], "┃  label         desk_       ┃");
+        assert_eq!(lines[7], "┃  workspace     none        ┃");
+    }
+}
@@ -1,6 +1,57 @@
 #[cfg(test)]
 pub mod tests {
+    use std::cell::RefCell;
+    use std::io;
+    use ratatui::buffer::Buffer;
+    use crate::configuration::Configuration;
+    use crate::map::{Map, Viewport};
     use crate::monitor::{Monitor, Resolution, Position};
+    use crate::runner::CommandRunner;
+    use crate::utils::{ColorSupport, LabelAlign, TUIMode};
+
+    // Records every call and answers with the canned stdout of the program,
+    // or fails with its canned stderr
+    #[derive(Debug, Default)]
+    pub struct FakeRunner {
+        pub outputs: Vec<(String, String)>,
+        pub failures: Vec<(String, String)>,
+        pub calls: RefCell<Vec<(String, Vec<String>)>>,
+    }
+
+    impl FakeRunner {
+        pub fn with_output(program: &str, stdout: &str) -> Self {
+            FakeRunner {
+                outputs: vec![(program.to_string(), stdout.to_string())],
+                ..Default::default()
+            }
+        }
+
+        pub fn with_failure(program: &str, stderr: &str) -> Self {
+            FakeRunner {
+                failures: vec![(program.to_string(), stderr.to_string())],
+                ..Default::default()
+            }
+        }
+    }
+
+    impl CommandRunner for FakeRunner {
+        fn run(&self, program: &str, args: &[String]) -> io::Result<String> {
+            self.calls.borrow_mut().push((program.to_string(), args.to_vec()));
+            if let Some((_, stderr)) = self.failures.iter().find(|(p, _)| p == program) {
+                return Err(io::Error::other(stderr.clone()));
+            }
+            Ok(self.outputs.iter()
+                .find(|(p, _)| p == program)
+                .map(|(_, stdout)| stdout.clone())
+                .unwrap_or_default())
+        }
+    }
+
+    // The symbols of one rendered row, for asserting on drawn text
+    pub fn row_text(buf: &Buffer, y: u16) -> String {
+        (0..buf.area.width).map(|x| buf[(x, y)].symbol().to_string()).collect()
+    }
+
     pub fn test_monitors() -> Vec<Monitor> {
         vec![
             Monitor {
@@ -8,29 +59,60 @@ pub mod tests {
                 description: Some("Description 1".to_string()),
                 enabled: true,
                 modes: vec![
-                    Resolution { width: 1920, height: 1080, refresh:60.0, preferred: true ,current: true},
-                    Resolution { width: 1280, height: 720 , refresh:60.0, preferred: false,current: false},
+                    Resolution { width: 1920, height: 1080, refresh:60.0, preferred: true ,current: true, interlaced: false, custom: false },
+                    Resolution { width: 1280, height: 720 , refresh:60.0, preferred: false,current: false, interlaced: false, custom: false },
                 ],
                 position: Some(Position { x: 0, y: 0 }),
                 scale: Some(1.0),
                 transform: None,
-                saved_position: None,
-                saved_scale: None,
+                ..Default::default()
             },
             Monitor {
                 name: "Monitor 2".to_string(),
                 description: Some("Description 2".to_string()),
                 enabled: false,
                 modes: vec![
-                    Resolution { width: 1920, height: 1080 , refresh:60.0, preferred: false, current: false },
-                    Resolution { width: 1280, height: 720 , refresh:60.0, preferred: true, current: true},
+                    Resolution { width: 1920, height: 1080 , refresh:60.0, preferred: false, current: false, interlaced: false, custom: false },
+                    Resolution { width: 1280, height: 720 , refresh:60.0, preferred: true, current: true, interlaced: false, custom: false },
                 ],
                 position: Some(Position { x: 1920, y: 0 }),
                 scale: Some(1.25),
                 transform: None,
-                saved_position: None,
-                saved_scale: None,
+                ..Default::default()
             },
         ]
     }
-}   
+
+    // Config keeping the monitor state and the Hyprland config in an empty
+    // temp directory of its own, so tests don't read or move the user's.
+    pub fn test_config(name: &str) -> Configuration {
+        let dir = std::env::temp_dir().join(format!("display-tui-{}", name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).expect("Failed to create test dir");
+        Configuration {
+            monitors_config_path: dir.join("monitors.conf").to_str().unwrap().to_string(),
+            state_path: Some(dir.join("monitor_state.json")),
+            ..Default::default()
+        }
+    }
+
+    impl<'a> Map<'a> {
+        // The map as drawn in View mode with the default config, tests
+        // override the fields they check with struct update syntax.
+        pub fn for_test(monitors: &'a Vec<Monitor>) -> Self {
+            Map {
+                mode: TUIMode::View,
+                selected: 0,
+                monitors,
+                grid: false,
+                viewport: Viewport::default(),
+                margin: 50.0,
+                preview_scale: None,
+                pulse: false,
+                label_align: LabelAlign::TopLeft,
+                colors: ColorSupport::Full,
+                footprint: false,
+            }
+        }
+    }
+}
@@ -1,10 +1,208 @@
+use crossterm::event::KeyCode;
+use serde::{Deserialize, Serialize};
+
 #[derive(Default,Debug, Clone, Copy, PartialEq)]
 pub enum TUIMode {
     #[default]
     View,
     Move,
     Resolution,
-    Scale
+    Scale,
+    Settings,
+    Arrange,
+    Config,
+}
+
+#[derive(Default,Debug, Clone, Copy, PartialEq)]
+pub enum LayoutPreset {
+    #[default]
+    MapAndList,
+    MapOnly,
+    ListOnly,
+}
+
+impl LayoutPreset {
+    pub fn cycle(&self) -> Self {
+        match self {
+            LayoutPreset::MapAndList => LayoutPreset::MapOnly,
+            LayoutPreset::MapOnly => LayoutPreset::ListOnly,
+            LayoutPreset::ListOnly => LayoutPreset::MapAndList,
+        }
+    }
+}
+
+// Positions as written in the config (layout pixels, scaled) or in the
+// monitor's own pixels.
+#[derive(Default,Debug, Clone, Copy, PartialEq)]
+pub enum Coordinates {
+    #[default]
+    Logical,
+    Native,
+}
+
+impl Coordinates {
+    pub fn toggle(&self) -> Self {
+        match self {
+            Coordinates::Logical => Coordinates::Native,
+            Coordinates::Native => Coordinates::Logical,
+        }
+    }
+
+    // `value` is a logical coordinate of a monitor at `scale`
+    pub fn convert(&self, value: i32, scale: f32) -> i32 {
+        match self {
+            Coordinates::Logical => value,
+            Coordinates::Native => (value as f64 * scale as f64).round() as i32,
+        }
+    }
+
+    // Logical distance of a `distance` nudge on a monitor at `scale`,
+    // never rounded down to no move at all.
+    pub fn nudge(&self, distance: i32, scale: f32) -> i32 {
+        match self {
+            Coordinates::Logical => distance,
+            Coordinates::Native => {
+                let logical = (distance as f64 / scale as f64).round() as i32;
+                if logical == 0 { distance.signum() } else { logical }
+            },
+        }
+    }
+
+    pub fn unit(&self) -> &'static str {
+        match self {
+            Coordinates::Logical => "logical",
+            Coordinates::Native => "native",
+        }
+    }
+}
+
+// Keys moving the selection and the monitors: hjkl, the arrows or both.
+#[derive(Default,Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MovementKeys {
+    Vim,
+    Arrows,
+    #[default]
+    Both,
+}
+
+impl MovementKeys {
+    // Keys other than hjkl and the arrows are always allowed.
+    pub fn allows(&self, code: KeyCode) -> bool {
+        match code {
+            KeyCode::Char(c) if "hjkl".contains(c) => *self != MovementKeys::Arrows,
+            KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right => *self != MovementKeys::Vim,
+            _ => true,
+        }
+    }
+}
+
+// Where the name sits inside a monitor on the map
+#[derive(Default,Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LabelAlign {
+    #[default]
+    TopLeft,
+    Center,
+}
+
+// Colors the terminal shows. With only the 8/16 basic ones the map marks
+// the selection with reversed text instead of telling yellow from blue.
+#[derive(Default,Debug, Clone, Copy, PartialEq)]
+pub enum ColorSupport {
+    Basic,
+    #[default]
+    Full,
+}
+
+impl ColorSupport {
+    // COLORTERM=truecolor/24bit, or a TERM ending in 256color
+    pub fn from_env(colorterm: Option<&str>, term: Option<&str>) -> Self {
+        let truecolor = colorterm.is_some_and(|c| c == "truecolor" || c == "24bit");
+        let colors_256 = term.is_some_and(|t| t.ends_with("256color"));
+        if truecolor || colors_256 { ColorSupport::Full } else { ColorSupport::Basic }
+    }
+
+    pub fn detect() -> Self {
+        ColorSupport::from_env(
+            std::env::var("COLORTERM").ok().as_deref(),
+            std::env::var("TERM").ok().as_deref(),
+        )
+    }
+}
+
+#[derive(Default,Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConfigFormat {
+    #[default]
+    Hyprland,
+    // `monitorv2 { ... }` blocks
+    HyprlandV2,
+    Sway,
+    Niri,
+}
+
+impl ConfigFormat {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "hyprland" => Some(ConfigFormat::Hyprland),
+            "hyprland-v2" => Some(ConfigFormat::HyprlandV2),
+            "sway" => Some(ConfigFormat::Sway),
+            "niri" => Some(ConfigFormat::Niri),
+            _ => None,
+        }
+    }
+}
+
+// Step through a list of `len` items, wrapping at the ends or stopping there.
+pub fn next_index(selected: usize, len: usize, wrap: bool) -> usize {
+    if selected + 1 < len {
+        selected + 1
+    } else if wrap {
+        0
+    } else {
+        len.saturating_sub(1)
+    }
+}
+
+pub fn previous_index(selected: usize, len: usize, wrap: bool) -> usize {
+    if selected > 0 {
+        selected - 1
+    } else if wrap {
+        len.saturating_sub(1)
+    } else {
+        0
+    }
+}
+
+// Rows jumped by PageUp/PageDown in the side lists, clamped to the ends.
+pub const PAGE_SIZE: usize = 5;
+
+pub fn page_down(selected: usize, len: usize) -> usize {
+    (selected + PAGE_SIZE).min(len.saturating_sub(1))
+}
+
+pub fn page_up(selected: usize) -> usize {
+    selected.saturating_sub(PAGE_SIZE)
+}
+
+// Last modification of the file at `path`, None when it doesn't exist.
+pub fn modified_time(path: &str) -> Option<std::time::SystemTime> {
+    std::fs::metadata(shellexpand::tilde(path).as_ref())
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+// Wall clock as HH:MM UTC, the local offset would need the time zone database.
+pub fn format_clock(time: std::time::SystemTime) -> String {
+    let seconds = time.duration_since(std::time::UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    format!("{:02}:{:02} UTC", seconds / 3600 % 24, seconds / 60 % 60)
+}
+
+// Elapsed time as H:MM:SS
+pub fn format_duration(duration: std::time::Duration) -> String {
+    let seconds = duration.as_secs();
+    format!("{}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
 }
 
 pub struct ScaleValue {
@@ -29,3 +227,16 @@ impl ScaleValue {
         ]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn clock_and_duration() {
+        // 2024-01-01 13:05:09 UTC
+        assert_eq!(format_clock(UNIX_EPOCH + Duration::from_secs(1_704_114_309)), "13:05 UTC");
+        assert_eq!(format_duration(Duration::from_secs(3 * 3600 + 4 * 60 + 5)), "3:04:05");
+    }
+}